@@ -1,26 +1,57 @@
 //! A CLI tool for validating supply chain metadata documents.
 //!
 //! This tool currently supports validating In-Toto v1 documents with
-//! SLSA Provenance v1 predicates.
+//! SLSA Provenance v1 predicates, optionally wrapped in DSSE envelopes,
+//! signing and verifying DSSE envelopes with local keys, verifying provenance
+//! against policies, and generating provenance from CI environments.
 //! TODO(mlieberman85): The CLI commands and args could probably be generalized better to minimize duplication.
 
-use std::{path::PathBuf, process};
+use std::{collections::HashMap, path::PathBuf, process, sync::Arc};
 
 use anyhow::Result;
-use clap::{Parser, ValueEnum};
-use serde::de::DeserializeOwned;
-use serde_json::Value;
+use clap::{Args, Parser, ValueEnum};
+use schemars::JsonSchema as _;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
 use spector::{
+    convert::slsa::statement_v02_to_v1,
+    crypto::{
+        digest::{digest_file, DigestRequest},
+        keys::{PrivateKey, PublicKey},
+    },
+    generate::{generate_provenance, CiEnvironment},
+    input::{expand_inputs, Input},
     models::{
+        dsse::envelope::Envelope,
         intoto::{
-            predicate::Predicate, provenance::SLSAProvenanceV1Predicate,
-            statement::InTotoStatementV1,
+            predicate::Predicate,
+            provenance::ResourceDescriptor,
+            registry::{self, PredicateRegistration},
+            statement::{Algorithm, DigestSet, InTotoStatementV1, STATEMENT_TYPE_V1},
+            vsa::SLSA_VSA_V1_PREDICATE_TYPE,
         },
-        sbom::{spdx22::Spdx22Document, spdx23::Spdx23},
+        sbom::spdx22::Spdx22Document,
+    },
+    sign::sign_statement,
+    validate::{
+        self,
+        batch::validate_many,
+        detect::validate_detected,
+        pipeline::{validate_statement, validate_value},
+        report::{Diagnostic, ValidationReport},
+        sarif::sarif_log,
+        spdx::validate_spdx23,
+        Validator,
+    },
+    verify::{
+        dsse::{verify_envelope, TrustedKey},
+        provenance::{verify_provenance, ProvenancePolicy},
+        slsa_level::{assess_build_level, BuildLevelConfig},
+        subject::verify_subjects,
     },
-    validate::{self, GenericValidator, Validator},
 };
 use typify::{TypeSpace, TypeSpaceSettings};
+use url::Url;
 
 #[derive(Parser)]
 #[clap(
@@ -39,6 +70,13 @@ enum Command {
     SchemaGenerate(SchemaGenerate),
     CodeGenerate(CodeGenerate),
     SchemaValidate(SchemaValidate),
+    Verify(Verify),
+    Sign(Sign),
+    VerifySubject(VerifySubject),
+    Convert(Convert),
+    VerifyProvenance(VerifyProvenance),
+    SlsaLevel(SlsaLevel),
+    Generate(Generate),
 }
 
 // The `code-generate` subcommand
@@ -56,9 +94,7 @@ enum CodeGenerateSubCommand {
 
 #[derive(Parser)]
 struct JsonSchema {
-    /// Path to the file to generate a schema for
-    /// TODO(mlieberman85): Make this optional once we support stdin
-    /// TODO(mlieberman85): Figure out how to generalize this to all applicable subcommands
+    /// Path to the JSON schema to generate code from, or `-` for stdin
     #[clap(value_parser)]
     #[clap(long, short, required = true)]
     file: PathBuf,
@@ -67,10 +103,58 @@ struct JsonSchema {
 // The `validate` subcommand
 #[derive(Parser)]
 struct Validate {
+    #[clap(flatten)]
+    output: OutputArgs,
+
     #[clap(subcommand)]
     document: ValidateDocumentSubCommand,
 }
 
+// How to validate and report documents, shared by the subcommands validating documents
+#[derive(Args, Clone, Copy)]
+struct OutputArgs {
+    /// Format of the validation results
+    #[arg(value_enum)]
+    #[clap(long, global = true, default_value = "text")]
+    output: OutputFormat,
+
+    /// Number of files to validate concurrently, 0 for one per core
+    #[clap(long, short, global = true, default_value_t = 0)]
+    jobs: usize,
+}
+
+// The supported formats of validation results
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human readable messages, printing the document if valid
+    Text,
+    /// A JSON object listing the diagnostics of each document
+    Json,
+    /// A SARIF 2.1.0 log for code scanning tools
+    Sarif,
+}
+
+// The documents to validate, shared by the subcommands validating documents
+#[derive(Args)]
+#[group(required = true, multiple = true)]
+struct InputArgs {
+    /// Path to a file to validate, `-` for stdin, a directory of JSON files or a glob pattern
+    #[clap(value_parser)]
+    #[clap(long, short)]
+    file: Vec<PathBuf>,
+
+    /// Further files, directories or glob patterns to validate
+    #[clap(value_parser)]
+    files: Vec<PathBuf>,
+}
+
+impl InputArgs {
+    /// Expands the arguments into the documents to validate.
+    fn expand(&self) -> Result<Vec<Input>> {
+        expand_inputs(&[self.file.as_slice(), self.files.as_slice()].concat())
+    }
+}
+
 // The `generate` subcommand
 #[derive(Parser)]
 struct SchemaGenerate {
@@ -85,8 +169,196 @@ struct SchemaValidate {
     #[clap(value_parser)]
     schema: PathBuf,
 
-    /// Path to the file to validate
-    // TODO(mlieberman85): Make this optional once we support stdin
+    #[clap(flatten)]
+    input: InputArgs,
+
+    #[clap(flatten)]
+    output: OutputArgs,
+}
+
+// The `verify` subcommand
+#[derive(Parser)]
+struct Verify {
+    /// Path to a PEM-encoded public key trusted to sign the envelope
+    #[clap(value_parser)]
+    #[clap(long, short, required = true)]
+    key: Vec<PathBuf>,
+
+    /// Minimum number of distinct keys that must verify a signature
+    #[clap(long, short, default_value_t = 1)]
+    threshold: usize,
+
+    /// Predicate type for the In-Toto v1 statement in the envelope payload, by alias, name or URI
+    #[clap(long, short, value_parser = parse_predicate)]
+    predicate: Option<Arc<PredicateRegistration>>,
+
+    /// Path to the DSSE envelope to verify
+    #[clap(value_parser)]
+    #[clap(long, short, required = true)]
+    file: PathBuf,
+}
+
+// The `sign` subcommand
+#[derive(Parser)]
+struct Sign {
+    /// Path to a PEM-encoded Ed25519 or ECDSA P-256 private key
+    #[clap(value_parser)]
+    #[clap(long, short, required = true)]
+    key: PathBuf,
+
+    /// Key ID to record alongside the signature in the envelope
+    #[clap(long)]
+    keyid: Option<String>,
+
+    /// Path to the In-Toto v1 statement to sign
+    #[clap(value_parser)]
+    #[clap(long, short, required = true)]
+    file: PathBuf,
+}
+
+// The `verify-subject` subcommand
+#[derive(Parser)]
+struct VerifySubject {
+    /// Path to the In-Toto v1 statement, or DSSE envelope wrapping one
+    #[clap(value_parser)]
+    #[clap(long, short, required = true)]
+    statement: PathBuf,
+
+    /// Paths to the artifacts to verify against the statement subjects
+    #[clap(value_parser, required = true)]
+    artifacts: Vec<PathBuf>,
+}
+
+// The `verify-provenance` subcommand
+#[derive(Parser)]
+struct VerifyProvenance {
+    /// Path to the In-Toto v1 statement with a SLSA Provenance v1 predicate, or DSSE envelope
+    /// wrapping one
+    #[clap(value_parser)]
+    #[clap(long, short, required = true)]
+    statement: PathBuf,
+
+    /// Path to a JSON provenance policy, extended by the other policy options
+    #[clap(value_parser)]
+    #[clap(long, short)]
+    policy: Option<PathBuf>,
+
+    /// Trusted builder ID
+    #[clap(long)]
+    builder_id: Vec<String>,
+
+    /// Accepted build type
+    #[clap(long)]
+    build_type: Vec<String>,
+
+    /// Expected external parameter as KEY=VALUE, where VALUE is parsed as JSON if possible
+    #[clap(long, short, value_parser = parse_external_parameter)]
+    external_parameter: Vec<(String, Value)>,
+
+    /// Path to a PEM-encoded public key trusted to sign the envelope, required for envelopes
+    #[clap(value_parser)]
+    #[clap(long, short)]
+    key: Vec<PathBuf>,
+
+    /// Minimum number of distinct keys that must verify a signature
+    #[clap(long, short, default_value_t = 1)]
+    threshold: usize,
+
+    /// Pass even if no artifacts are given to match the statement subjects against
+    #[clap(long)]
+    allow_no_subject: bool,
+
+    /// Paths to the artifacts to verify against the statement subjects
+    #[clap(value_parser)]
+    artifacts: Vec<PathBuf>,
+}
+
+// The `slsa-level` subcommand
+#[derive(Parser)]
+struct SlsaLevel {
+    /// Path to the In-Toto v1 statement with a SLSA Provenance v1 predicate, or DSSE envelope
+    /// wrapping one
+    #[clap(value_parser)]
+    #[clap(long, short, required = true)]
+    statement: PathBuf,
+
+    /// Path to a JSON configuration of the builders trusted at each level
+    #[clap(value_parser)]
+    #[clap(long, short, required = true)]
+    config: PathBuf,
+
+    /// Path to a PEM-encoded public key trusted to sign the envelope
+    #[clap(value_parser)]
+    #[clap(long, short)]
+    key: Vec<PathBuf>,
+
+    /// Minimum number of distinct keys that must verify a signature
+    #[clap(long, short, default_value_t = 1)]
+    threshold: usize,
+
+    /// Print a VSA statement for the assessment instead of a report
+    #[clap(long, requires_all = ["verifier_id", "resource_uri"])]
+    vsa: bool,
+
+    /// Verifier ID recorded in the VSA
+    #[clap(long)]
+    verifier_id: Option<Url>,
+
+    /// URI of the artifact recorded in the VSA
+    #[clap(long)]
+    resource_uri: Option<Url>,
+}
+
+// The `generate` subcommand
+#[derive(Parser)]
+struct Generate {
+    #[clap(subcommand)]
+    document: GenerateSubCommand,
+}
+
+// The supported generated document types
+#[derive(Parser)]
+enum GenerateSubCommand {
+    Provenance(GenerateProvenance),
+}
+
+// The provenance generate subcommand
+#[derive(Parser)]
+struct GenerateProvenance {
+    /// CI environment to read the build from
+    #[arg(value_enum)]
+    #[clap(long, required = true)]
+    ci: CiOption,
+
+    /// Paths to the artifacts to record as subjects
+    #[clap(value_parser, required = true)]
+    artifacts: Vec<PathBuf>,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum CiOption {
+    GithubActions,
+    GitlabCi,
+}
+
+// The `convert` subcommand
+#[derive(Parser)]
+struct Convert {
+    #[clap(subcommand)]
+    conversion: ConvertSubCommand,
+}
+
+// The supported conversions
+#[derive(Parser)]
+enum ConvertSubCommand {
+    #[clap(name = "slsa-v0.2-to-v1")]
+    SlsaV02ToV1(ConvertSlsaV02ToV1),
+}
+
+// The SLSA Provenance v0.2 to v1 convert subcommand
+#[derive(Parser)]
+struct ConvertSlsaV02ToV1 {
+    /// Path to the In-Toto statement with a SLSA Provenance v0.2 predicate
     #[clap(value_parser)]
     #[clap(long, short, required = true)]
     file: PathBuf,
@@ -96,8 +368,12 @@ struct SchemaValidate {
 #[derive(Parser)]
 enum ValidateDocumentSubCommand {
     InTotoV1(ValidateInTotoV1),
+    #[clap(alias = "spdx-v23")]
     SPDXV23(ValidateSPDXV23),
+    #[clap(alias = "spdx-v22")]
     SPDXV22(ValidateSPDXV22),
+    Dsse(ValidateDsse),
+    Auto(ValidateAuto),
 }
 
 // The supported schema generate document types
@@ -107,63 +383,108 @@ enum GenerateDocumentSubCommand {
 }
 
 // The In-Toto v1 validate document subcommand
-// TODO(mlieberman85): Add support for stdin
-// TODO(mlieberman85): Figure out a way to ensure file is added onto all document subcommands
 #[derive(Parser)]
 struct ValidateInTotoV1 {
-    /// Predicate type for In-Toto v1 documents
-    #[arg(value_enum)]
-    #[clap(long, short)]
-    predicate: Option<PredicateOption>,
+    /// Predicate type for In-Toto v1 documents, by alias, name or URI
+    #[clap(long, short, value_parser = parse_predicate)]
+    predicate: Option<Arc<PredicateRegistration>>,
 
-    /// Path to the file to validate
-    #[clap(value_parser)]
-    #[clap(long, short, required = true)]
-    file: PathBuf,
+    /// Also accept the legacy `https://in-toto.io/Statement/v0.1` _type, reporting it as a warning
+    #[clap(long)]
+    lenient: bool,
+
+    #[clap(flatten)]
+    input: InputArgs,
 }
 
 // The SPDX v2.3 validate document subcommand
 #[derive(Parser)]
 struct ValidateSPDXV23 {
-    /// Path to the file to validate
-    #[clap(value_parser)]
-    #[clap(long, short, required = true)]
-    file: PathBuf,
+    #[clap(flatten)]
+    input: InputArgs,
 }
 
 // The SPDX v2.2 validate document subcommand
 #[derive(Parser)]
 struct ValidateSPDXV22 {
-    /// Path to the file to validate
-    #[clap(value_parser)]
-    #[clap(long, short, required = true)]
-    file: PathBuf,
+    #[clap(flatten)]
+    input: InputArgs,
 }
 
-// The In-Toto v1 generate schema subcommand
+// The DSSE envelope validate document subcommand
 #[derive(Parser)]
-struct GenerateInTotoV1 {
-    /// Predicate type for In-Toto v1 documents
-    #[arg(value_enum)]
-    #[clap(long, short)]
-    predicate: Option<PredicateOption>,
+struct ValidateDsse {
+    /// Predicate type for the In-Toto v1 statement in the envelope payload, by alias, name or URI
+    #[clap(long, short, value_parser = parse_predicate)]
+    predicate: Option<Arc<PredicateRegistration>>,
+
+    #[clap(flatten)]
+    input: InputArgs,
 }
 
-#[derive(Copy, Clone, ValueEnum)]
-enum PredicateOption {
-    SLSAProvenanceV1,
+// The validate subcommand detecting the document type
+#[derive(Parser)]
+struct ValidateAuto {
+    #[clap(flatten)]
+    input: InputArgs,
+}
+
+// The In-Toto v1 generate schema subcommand
+#[derive(Parser)]
+struct GenerateInTotoV1 {
+    /// Predicate type for In-Toto v1 documents, by alias, name or URI
+    #[clap(long, short, value_parser = parse_predicate)]
+    predicate: Option<Arc<PredicateRegistration>>,
 }
 
 #[derive(Parser)]
 struct SLSAProvenanceV1 {}
 
+/// Parses a `--external-parameter` KEY=VALUE pair.
+fn parse_external_parameter(s: &str) -> Result<(String, Value)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("expected KEY=VALUE, found {:?}", s))?;
+    let value =
+        serde_json::from_str::<Value>(value).unwrap_or_else(|_| Value::String(value.to_string()));
+    Ok((key.to_string(), value))
+}
+
+/// Resolves a `--predicate` value to a registered predicate type.
+fn parse_predicate(key: &str) -> Result<Arc<PredicateRegistration>> {
+    registry::lookup(key).ok_or_else(|| {
+        let aliases = registry::registrations()
+            .iter()
+            .map(|r| r.alias.clone())
+            .collect::<Vec<_>>();
+        anyhow::anyhow!(
+            "unknown predicate type {:?}, expected a URI, name or one of: {}",
+            key,
+            aliases.join(", ")
+        )
+    })
+}
+
 /// Validates the specified document.
 fn validate_cmd(validate: Validate) -> Result<()> {
     //let file_str = std::fs::read_to_string(&validate.file)?;
+    let output = validate.output;
     match validate.document {
-        ValidateDocumentSubCommand::InTotoV1(in_toto) => validate_intoto_v1(in_toto),
-        ValidateDocumentSubCommand::SPDXV23(spdx) => validate_document::<Spdx23>(spdx.file),
-        ValidateDocumentSubCommand::SPDXV22(spdx) => validate_document::<Spdx22Document>(spdx.file),
+        ValidateDocumentSubCommand::InTotoV1(in_toto) => validate_intoto_v1(in_toto, output),
+        ValidateDocumentSubCommand::SPDXV23(spdx) => {
+            validate_document(spdx.input, output, validate_spdx23)
+        }
+        ValidateDocumentSubCommand::SPDXV22(spdx) => {
+            validate_document(spdx.input, output, |value| {
+                let document = validate_value::<Spdx22Document>(value)?;
+                Ok((
+                    document,
+                    ValidationReport::new(Spdx22Document::schema_name()),
+                ))
+            })
+        }
+        ValidateDocumentSubCommand::Dsse(dsse) => validate_dsse(dsse, output),
+        ValidateDocumentSubCommand::Auto(auto) => validate_auto(auto, output),
     }
 }
 
@@ -175,87 +496,624 @@ fn generate_cmd(generate: SchemaGenerate) -> Result<()> {
 }
 
 /// Handles validation for In-Toto v1 documents.
-fn validate_intoto_v1(in_toto: ValidateInTotoV1) -> Result<()> {
-    let file_str = std::fs::read_to_string(&in_toto.file)?;
-    let result = serde_json::from_str::<InTotoStatementV1>(&file_str);
+fn validate_intoto_v1(in_toto: ValidateInTotoV1, output: OutputArgs) -> Result<()> {
+    let validator = |value: &Value| -> ReportResult {
+        let (statement, mut report) = validate_statement(value, in_toto.lenient)?;
+        report.diagnostics.extend(check_predicate_type(
+            &statement,
+            in_toto.predicate.as_deref(),
+            "",
+        ));
+        into_result(report)
+    };
+    validate_inputs(
+        &in_toto.input,
+        output,
+        validator,
+        |source| match parse_json(source, &InTotoStatementV1::schema_name())
+            .and_then(|value| validate_statement(&value, in_toto.lenient))
+        {
+            Ok((statement, report)) => {
+                for warning in report.warnings() {
+                    eprintln!("Warning: {}", warning.message);
+                }
+                check_intoto_v1_predicate(statement, in_toto.predicate.as_deref())
+            }
+            Err(report) => Err(report_validation_errors(&report)),
+        },
+    )
+}
+
+/// Handles validation for DSSE envelopes wrapping In-Toto v1 statements.
+fn validate_dsse(dsse: ValidateDsse, output: OutputArgs) -> Result<()> {
+    let validator = |value: &Value| -> ReportResult {
+        let mut report = ValidationReport::new("Envelope");
+        match Envelope::deserialize(value) {
+            Ok(envelope) => match envelope.statement() {
+                Ok(statement) => report.diagnostics.extend(check_predicate_type(
+                    &statement,
+                    dsse.predicate.as_deref(),
+                    "/payload",
+                )),
+                Err(err) => report.diagnostics.push(Diagnostic::error(
+                    "/payload",
+                    "deserialize",
+                    err.to_string(),
+                )),
+            },
+            Err(err) => {
+                report
+                    .diagnostics
+                    .push(Diagnostic::error("", "deserialize", err.to_string()))
+            }
+        }
+        into_result(report)
+    };
+    validate_inputs(&dsse.input, output, validator, |source| {
+        let envelope = serde_json::from_str::<Envelope>(source).map_err(|err| {
+            eprintln!("Error parsing DSSE envelope: {}", err);
+            err
+        })?;
+
+        match envelope.statement() {
+            Ok(statement) => {
+                println!(
+                    "Valid DSSE envelope with {} signature(s)",
+                    envelope.signatures.len()
+                );
+                check_intoto_v1_predicate(statement, dsse.predicate.as_deref())
+            }
+            Err(err) => {
+                eprintln!("Error parsing DSSE payload: {}", err);
+                Err(err)
+            }
+        }
+    })
+}
 
-    match result {
-        Ok(statement) => {
-            let pretty_json = serde_json::to_string_pretty(&statement)?;
+/// Handles validation of documents of any supported type, detected from their contents.
+fn validate_auto(auto: ValidateAuto, output: OutputArgs) -> Result<()> {
+    // Reports name the detected type, so that machine readable output tells it too.
+    let validator = |value: &Value| -> ReportResult {
+        let (kind, mut report) = validate_detected(value);
+        if let Some(kind) = kind {
+            report.document_kind = kind.to_string();
+        }
+        into_result(report)
+    };
+    validate_inputs(&auto.input, output, validator, |source| {
+        let value = parse_json(source, "JSON value").map_err(|r| report_validation_errors(&r))?;
+        let (kind, report) = validate_detected(&value);
+        if let Some(kind) = kind {
+            println!("Detected {} document", kind);
+        }
+        for warning in report.warnings() {
+            eprintln!("Warning: {}", warning.message);
+        }
+        match report.is_valid() {
+            true => {
+                println!("Valid document");
+                Ok(())
+            }
+            false => Err(report_validation_errors(&report)),
+        }
+    })
+}
+
+/// Verifies the signatures of a DSSE envelope against local public keys.
+fn verify_cmd(verify: Verify) -> Result<()> {
+    let keys = read_trusted_keys(&verify.key)?;
+    let file_str = std::fs::read_to_string(&verify.file)?;
+    let envelope = serde_json::from_str::<Envelope>(&file_str)?;
+    let verified = verify_envelope(&envelope, &keys, verify.threshold)?;
+
+    for signature in &verified.verified_signatures {
+        println!(
+            "Verified signature with keyid {:?} using key {}",
+            signature.keyid.as_deref().unwrap_or(""),
+            signature.key_name
+        );
+    }
+    check_intoto_v1_predicate(verified.statement, verify.predicate.as_deref())
+}
+
+/// Reads PEM-encoded public keys, named after the paths they were read from.
+fn read_trusted_keys(paths: &[PathBuf]) -> Result<Vec<TrustedKey>> {
+    paths
+        .iter()
+        .map(|path| {
+            let pem = std::fs::read_to_string(path)?;
+            let key = PublicKey::from_pem(&pem)
+                .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+            Ok(TrustedKey {
+                name: path.display().to_string(),
+                key,
+            })
+        })
+        .collect()
+}
+
+/// Signs an In-Toto v1 statement and prints the resulting DSSE envelope.
+fn sign_cmd(sign: Sign) -> Result<()> {
+    let pem = std::fs::read_to_string(&sign.key)?;
+    let key =
+        PrivateKey::from_pem(&pem).map_err(|e| anyhow::anyhow!("{}: {}", sign.key.display(), e))?;
+
+    let file_str = std::fs::read_to_string(&sign.file)?;
+    let statement = serde_json::from_str::<InTotoStatementV1>(&file_str)?;
+    let envelope = sign_statement(&statement, &key, sign.keyid)?;
+
+    println!("{}", serde_json::to_string_pretty(&envelope)?);
+    Ok(())
+}
+
+/// Verifies local artifacts against the subjects of a statement.
+fn verify_subject_cmd(vs: VerifySubject) -> Result<()> {
+    let statement = read_statement(&vs.statement)?;
+    let report = verify_subjects(&statement.subject, &vs.artifacts)?;
+
+    for matched in &report.matched {
+        println!(
+            "Matched subject {:?} to artifact {}",
+            matched.subject,
+            matched.artifact.display()
+        );
+    }
+    for mismatch in &report.mismatches {
+        eprintln!(
+            "Digest mismatch for subject {:?} and artifact {}: {} expected {}, found {}",
+            mismatch.subject,
+            mismatch.artifact.display(),
+            mismatch.algorithm,
+            mismatch.expected,
+            mismatch.actual
+        );
+    }
+    for artifact in &report.unmatched_artifacts {
+        eprintln!("Unmatched artifact: {}", artifact.display());
+    }
+    for subject in &report.unmatched_subjects {
+        eprintln!("Unmatched subject: {:?}", subject);
+    }
+
+    if report.is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Subject verification failed: {} unmatched artifact(s), {} digest mismatch(es)",
+            report.unmatched_artifacts.len(),
+            report.mismatches.len()
+        ))
+    }
+}
+
+/// Verifies SLSA provenance against a policy, printing the result of each check.
+fn verify_provenance_cmd(vp: VerifyProvenance) -> Result<()> {
+    let mut policy = match &vp.policy {
+        Some(path) => serde_json::from_str::<ProvenancePolicy>(&std::fs::read_to_string(path)?)?,
+        None => ProvenancePolicy::default(),
+    };
+    policy.trusted_builders.extend(vp.builder_id);
+    policy.build_types.extend(vp.build_type);
+    policy.external_parameters.extend(vp.external_parameter);
+    policy.allow_no_subject |= vp.allow_no_subject;
+
+    // Provenance in an envelope is only trusted once its signatures are verified.
+    let value = serde_json::from_str::<Value>(&std::fs::read_to_string(&vp.statement)?)?;
+    let statement = match (value.get("payloadType").is_some(), vp.key.is_empty()) {
+        (true, true) => {
+            return Err(anyhow::anyhow!(
+                "{} is a DSSE envelope, pass --key to verify its signatures",
+                vp.statement.display()
+            ))
+        }
+        (true, false) => {
+            let keys = read_trusted_keys(&vp.key)?;
+            let envelope = serde_json::from_value::<Envelope>(value)?;
+            let verified = verify_envelope(&envelope, &keys, vp.threshold)?;
+            println!(
+                "PASSED signature: {} signature(s) verified",
+                verified.verified_signatures.len()
+            );
+            verified.statement
+        }
+        (false, true) => serde_json::from_value::<InTotoStatementV1>(value)?,
+        (false, false) => {
+            return Err(anyhow::anyhow!(
+                "{} is not a DSSE envelope, --key only applies to envelopes",
+                vp.statement.display()
+            ))
+        }
+    };
+    let report = verify_provenance(&statement, &policy, &vp.artifacts)?;
+
+    for check in &report.checks {
+        println!("{} {}: {}", check.status, check.name, check.message);
+    }
+
+    let failed = report.failed().count();
+    if failed == 0 {
+        println!("Provenance verification passed");
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Provenance verification failed: {} of {} check(s) failed",
+            failed,
+            report.checks.len()
+        ))
+    }
+}
+
+/// Assesses the SLSA Build level evidenced by provenance, printing a report or a VSA.
+fn slsa_level_cmd(sl: SlsaLevel) -> Result<()> {
+    let config = serde_json::from_str::<BuildLevelConfig>(&std::fs::read_to_string(&sl.config)?)?;
+
+    // The provenance is only authentic if its envelope signature was verified.
+    let (statement, signature_verified) = if sl.key.is_empty() {
+        (read_statement(&sl.statement)?, false)
+    } else {
+        let keys = read_trusted_keys(&sl.key)?;
+        let envelope = serde_json::from_str::<Envelope>(&std::fs::read_to_string(&sl.statement)?)?;
+        (
+            verify_envelope(&envelope, &keys, sl.threshold)?.statement,
+            true,
+        )
+    };
+    let assessment = assess_build_level(&statement, &config, signature_verified);
+
+    if let (true, Some(verifier_id), Some(resource_uri)) = (sl.vsa, sl.verifier_id, sl.resource_uri)
+    {
+        let policy = policy_descriptor(&sl.config)?;
+        let vsa = InTotoStatementV1 {
+            _type: Url::parse(STATEMENT_TYPE_V1)?,
+            subject: statement.subject,
+            predicate_type: Url::parse(SLSA_VSA_V1_PREDICATE_TYPE)?,
+            predicate: Predicate::SLSAVerificationSummaryV1(assessment.to_vsa(
+                verifier_id,
+                resource_uri,
+                policy,
+            )),
+        };
+        println!("{}", serde_json::to_string_pretty(&vsa)?);
+        return Ok(());
+    }
+
+    for requirement in &assessment.requirements {
+        println!(
+            "{} L{} {}: {}",
+            if requirement.satisfied {
+                "EVIDENCED"
+            } else {
+                "MISSING"
+            },
+            requirement.level,
+            requirement.name,
+            requirement.explanation
+        );
+    }
+    println!("SLSA Build level: {}", assessment.level);
+    println!(
+        "Verified levels: {}",
+        assessment.verified_levels().join(", ")
+    );
+    Ok(())
+}
+
+/// Describes a local policy file by its file URI and SHA-256 digest.
+fn policy_descriptor(path: &PathBuf) -> Result<ResourceDescriptor> {
+    let request = DigestRequest {
+        algorithm: Algorithm::Sha256,
+        hex_len: 64,
+    };
+    let digests = digest_file(path, std::slice::from_ref(&request))?;
+    let uri = Url::from_file_path(std::fs::canonicalize(path)?)
+        .map_err(|_| anyhow::anyhow!("Invalid policy path: {}", path.display()))?;
+
+    Ok(ResourceDescriptor::new(
+        Some(uri.into()),
+        Some(DigestSet::new(
+            digests
+                .into_iter()
+                .map(|(request, digest)| (request.algorithm, digest))
+                .collect(),
+        )?),
+    ))
+}
+
+/// Generates a document from the current environment and prints it.
+fn generate_document_cmd(generate: Generate) -> Result<()> {
+    match generate.document {
+        GenerateSubCommand::Provenance(provenance) => {
+            let ci = match provenance.ci {
+                CiOption::GithubActions => CiEnvironment::GithubActions,
+                CiOption::GitlabCi => CiEnvironment::GitlabCi,
+            };
+            let env = std::env::vars().collect::<HashMap<_, _>>();
+            let statement = generate_provenance(ci, &env, &provenance.artifacts)?;
+
+            println!("{}", serde_json::to_string_pretty(&statement)?);
+            Ok(())
+        }
+    }
+}
+
+/// Converts a document and prints the converted document.
+fn convert_cmd(convert: Convert) -> Result<()> {
+    match convert.conversion {
+        ConvertSubCommand::SlsaV02ToV1(c) => {
+            // SLSA Provenance v0.2 statements commonly use the legacy In-Toto v0.1 _type.
+            let file_str = std::fs::read_to_string(&c.file)?;
+            let value = serde_json::from_str::<Value>(&file_str)?;
+            let (statement, _) = InTotoStatementV1::from_value_lenient(&value)?;
+            let (converted, warnings) = statement_v02_to_v1(statement)?;
+
+            for warning in warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            println!("{}", serde_json::to_string_pretty(&converted)?);
+            Ok(())
+        }
+    }
+}
+
+/// Reads an In-Toto v1 statement from a file, unwrapping it if it is in a DSSE envelope.
+fn read_statement(path: &PathBuf) -> Result<InTotoStatementV1> {
+    let file_str = std::fs::read_to_string(path)?;
+    let value = serde_json::from_str::<Value>(&file_str)?;
+    if value.get("payloadType").is_some() {
+        serde_json::from_value::<Envelope>(value)?.statement()
+    } else {
+        Ok(serde_json::from_value::<InTotoStatementV1>(value)?)
+    }
+}
+
+/// Checks the predicate of a parsed In-Toto v1 statement against the requested predicate type.
+fn check_intoto_v1_predicate(
+    statement: InTotoStatementV1,
+    predicate: Option<&PredicateRegistration>,
+) -> Result<()> {
+    let pretty_json = serde_json::to_string_pretty(&statement)?;
+    let predicate_type = statement.predicate_type.as_str();
+    match predicate {
+        Some(expected) if expected.predicate_type != predicate_type => {
+            eprintln!("Invalid InTotoV1 {} document", expected.name);
+            eprintln!("Document: {}", &pretty_json);
+            Err(anyhow::anyhow!(
+                "Unexpected predicateType: {:?}",
+                predicate_type
+            ))
+        }
+        _ => {
             match statement.predicate {
-                Predicate::SLSAProvenanceV1(_) => match in_toto.predicate {
-                    Some(PredicateOption::SLSAProvenanceV1) => {
-                        println!("Valid InTotoV1 SLSAProvenanceV1 document");
-                        println!("Document: {}", &pretty_json);
-                        Ok(())
-                    }
-                    // TODO(mlieberman85): Uncomment below once additional predicate types are supported.
-                    /*Some(_) => {
-                        eprintln!("Invalid InTotoV1 SLSAProvenanceV1 document");
-                        eprintln!("Document: {}", &pretty_json);
-                        Err(anyhow::anyhow!(
-                            "Invalid InTotoV1 SLSAProvenanceV1 document"
-                        ))
-                    }*/
-                    None => {
-                        println!("Valid InTotoV1 SLSAProvenanceV1 document");
-                        println!("Document: {}", &pretty_json);
-                        Ok(())
-                    }
-                },
+                Predicate::Other(_) => println!("Unknown predicateType: {:?}", predicate_type),
                 _ => {
-                    if let Some(PredicateOption::SLSAProvenanceV1) = in_toto.predicate {
-                        eprintln!("Invalid InTotoV1 SLSAProvenanceV1 document");
-                        eprintln!("Document: {}", &pretty_json);
-                        Err(anyhow::anyhow!(
-                            "Unexpected predicateType: {:?}",
-                            statement.predicate_type.as_str()
-                        ))
-                    } else {
-                        println!(
-                            "Unknown predicateType: {:?}",
-                            statement.predicate_type.as_str()
-                        );
-                        println!("Document: {}", &pretty_json);
-                        Ok(())
-                    }
+                    let name = registry::lookup(predicate_type)
+                        .map(|r| r.name.clone())
+                        .unwrap_or_default();
+                    println!("Valid InTotoV1 {} document", name);
                 }
             }
-        }
-        Err(err) => {
-            // TODO(mlieberman85): Figure out how to add all the fields that are incorrect between a valid SLSA statement and the one that is being validated.
-            // Right now it only prints the first error.
-            eprintln!("Error parsing JSON: {}", err);
-            Err(err.into())
+            println!("Document: {}", &pretty_json);
+            Ok(())
         }
     }
 }
 
 /// Handles simpler validation of documents.
 /// TODO(mlieberman85): Over time this should handle the logic for validation of all document types.
-fn validate_document<T: DeserializeOwned>(file_path: PathBuf) -> Result<()> {
-    let file_str = std::fs::read_to_string(&file_path)?;
-    let file_value = serde_json::from_str::<Value>(&file_str)?;
-    let result = GenericValidator::<T>::new().validate(&file_value);
-
-    match result {
-        Ok(_) => {
-            let pretty_json = serde_json::to_string_pretty(&file_value)?;
-            println!("Valid document");
-            println!("Document: {}", &pretty_json);
-            Ok(())
+///
+/// `validate` validates a document against its model, and any further checks of its type,
+/// returning the document with its warnings.
+fn validate_document<T: schemars::JsonSchema>(
+    input: InputArgs,
+    output: OutputArgs,
+    validate: fn(&Value) -> ReportResult<(T, ValidationReport)>,
+) -> Result<()> {
+    let validator = |value: &Value| -> ReportResult { Ok(validate(value)?.1) };
+    validate_inputs(&input, output, validator, |source| {
+        match parse_json(source, &T::schema_name())
+            .and_then(|value| validate(&value).map(|(_, report)| (value, report)))
+        {
+            Ok((value, report)) => {
+                for warning in report.warnings() {
+                    eprintln!("Warning: {}", warning.message);
+                }
+                let pretty_json = serde_json::to_string_pretty(&value)?;
+                println!("Valid document");
+                println!("Document: {}", &pretty_json);
+                Ok(())
+            }
+            Err(report) => Err(report_validation_errors(&report)),
+        }
+    })
+}
+
+/// The result of validating a document, with its report as the error if it is invalid.
+type ReportResult<T = ValidationReport> = std::result::Result<T, ValidationReport>;
+
+/// Returns the report of a document as an error if it has errors.
+fn into_result(report: ValidationReport) -> ReportResult {
+    match report.is_valid() {
+        true => Ok(report),
+        false => Err(report),
+    }
+}
+
+/// Validates every input of a subcommand, failing if any is invalid.
+///
+/// A single input in text output is passed to `print`, which prints the messages of the
+/// subcommand and fails if the document is invalid. Otherwise the inputs are validated
+/// concurrently by `validator` with the requested number of jobs, and the reports are
+/// printed in the order of the inputs: in text output as a result per file followed by a
+/// summary.
+fn validate_inputs<V>(
+    input: &InputArgs,
+    OutputArgs { output, jobs }: OutputArgs,
+    validator: V,
+    print: impl Fn(&str) -> Result<()>,
+) -> Result<()>
+where
+    V: Validator<Output = ValidationReport> + Sync,
+{
+    let inputs = input.expand()?;
+    if output == OutputFormat::Text && inputs.len() == 1 {
+        return print(&inputs[0].read_to_string()?);
+    }
+
+    // Sources are only needed to locate diagnostics in SARIF output.
+    let keep_sources = output == OutputFormat::Sarif;
+    let reports = validate_many(&validator, &inputs, jobs, keep_sources)?
+        .into_iter()
+        .map(|result| {
+            let report = match result.result {
+                Ok(report) | Err(report) => report,
+            };
+            let source = result.source.unwrap_or_default();
+            (result.input.to_string(), source, report)
+        })
+        .collect::<Vec<_>>();
+    if output != OutputFormat::Text {
+        return print_reports(output, &reports);
+    }
+
+    let mut failed = 0;
+    for (name, _, report) in &reports {
+        for warning in report.warnings() {
+            eprintln!("{}: Warning: {}", name, warning.message);
         }
-        Err(err) => {
-            eprintln!("Error parsing JSON: {}", err);
-            Err(err.into())
+        if report.is_valid() {
+            println!("{}: valid", name);
+            continue;
         }
+        failed += 1;
+        for error in report.errors() {
+            eprintln!("{}: {}", name, error);
+        }
+        eprintln!(
+            "{}: invalid: {} problem(s) found",
+            name,
+            report.errors().count()
+        );
+    }
+    println!(
+        "{} of {} file(s) valid",
+        inputs.len() - failed,
+        inputs.len()
+    );
+    match failed {
+        0 => Ok(()),
+        _ => Err(anyhow::anyhow!(
+            "{} of {} file(s) failed validation",
+            failed,
+            inputs.len()
+        )),
+    }
+}
+
+/// Parses a JSON document, reporting a syntax error as a diagnostic.
+fn parse_json(source: &str, document_kind: &str) -> std::result::Result<Value, ValidationReport> {
+    serde_json::from_str::<Value>(source).map_err(|err| {
+        let mut report = ValidationReport::new(document_kind);
+        report
+            .diagnostics
+            .push(Diagnostic::error("", "syntax", err.to_string()));
+        report
+    })
+}
+
+/// Returns an error diagnostic if a statement doesn't have the requested predicate type.
+///
+/// `prefix` is the JSON Pointer of the statement within the validated document.
+fn check_predicate_type(
+    statement: &InTotoStatementV1,
+    predicate: Option<&PredicateRegistration>,
+    prefix: &str,
+) -> Option<Diagnostic> {
+    let predicate_type = statement.predicate_type.as_str();
+    predicate
+        .filter(|expected| expected.predicate_type != predicate_type)
+        .map(|expected| Diagnostic {
+            expected: Some(expected.predicate_type.clone()),
+            value: Some(Value::String(predicate_type.to_string())),
+            ..Diagnostic::error(
+                format!("{}/predicateType", prefix),
+                "const",
+                format!("Unexpected predicateType: {:?}", predicate_type),
+            )
+        })
+}
+
+/// Prints the reports of validated files as JSON or SARIF, failing if any has errors.
+///
+/// Each report is given with the name of the validated file and its contents, which are
+/// only used for SARIF output.
+fn print_reports(
+    output: OutputFormat,
+    reports: &[(String, String, ValidationReport)],
+) -> Result<()> {
+    let uris = reports
+        .iter()
+        .map(|(name, _, _)| name.replace('\\', "/"))
+        .collect::<Vec<_>>();
+    let valid = reports
+        .iter()
+        .filter(|(_, _, report)| report.is_valid())
+        .count();
+    let document = match output {
+        OutputFormat::Sarif => sarif_log(
+            reports
+                .iter()
+                .zip(&uris)
+                .map(|((_, source, report), uri)| (uri.as_str(), source.as_str(), report)),
+        ),
+        _ => json!({
+            "valid": valid == reports.len(),
+            "summary": {
+                "files": reports.len(),
+                "valid": valid,
+                "invalid": reports.len() - valid,
+            },
+            "documents": reports
+                .iter()
+                .zip(&uris)
+                .map(|((_, _, report), uri)| {
+                    json!({
+                        "file": uri,
+                        "valid": report.is_valid(),
+                        "documentKind": report.document_kind,
+                        "diagnostics": report.diagnostics,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        }),
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+
+    let errors = reports
+        .iter()
+        .map(|(_, _, report)| report.errors().count())
+        .sum::<usize>();
+    match errors {
+        0 => Ok(()),
+        _ => Err(anyhow::anyhow!("{} problem(s) found", errors)),
     }
 }
 
+/// Prints every problem found validating a document, returning the error to fail with.
+fn report_validation_errors(report: &ValidationReport) -> anyhow::Error {
+    for error in report.errors() {
+        eprintln!("Error parsing JSON: {}", error);
+    }
+    anyhow::anyhow!("{} problem(s) found", report.errors().count())
+}
+
 /// Handles generation of schemas for In-Toto v1 documents.
 fn generate_intoto_v1(in_toto: GenerateInTotoV1) -> Result<()> {
     match in_toto.predicate {
-        Some(PredicateOption::SLSAProvenanceV1) => print_schema::<SLSAProvenanceV1Predicate>(),
+        Some(registration) => {
+            println!("{}", serde_json::to_string_pretty(&registration.schema)?);
+            Ok(())
+        }
         None => print_schema::<InTotoStatementV1>(),
     }
 }
@@ -264,7 +1122,7 @@ fn generate_intoto_v1(in_toto: GenerateInTotoV1) -> Result<()> {
 fn code_generate_cmd(cg: CodeGenerate) -> Result<()> {
     match cg.codegen {
         CodeGenerateSubCommand::JsonSchema(json_schema) => {
-            let schema_str = std::fs::read_to_string(&json_schema.file)?;
+            let schema_str = Input::from(json_schema.file.as_path()).read_to_string()?;
             generate_rust_code(schema_str)
         }
     }
@@ -306,32 +1164,39 @@ fn print_schema<T: serde::Serialize + schemars::JsonSchema>() -> Result<()> {
 ///
 /// Prints the document if valid, otherwise prints an error message
 fn schema_validate_cmd<T: DeserializeOwned>(sv: SchemaValidate) -> Result<()> {
-    let file_str = std::fs::read_to_string(&sv.file)?;
     let schema_str = std::fs::read_to_string(&sv.schema)?;
     let schema = serde_json::from_str::<serde_json::Value>(&schema_str)?;
-    let validator = validate::JSONSchemaValidator::<Value>::new(&schema);
-    let document = serde_json::from_str::<serde_json::Value>(&file_str)?;
-    let result: std::result::Result<Value, anyhow::Error> = validator.validate(&document);
-
-    match result {
-        Ok(_) => {
-            println!("Valid document based on JSON schema");
-            match serde_json::from_value::<T>(document) {
-                Ok(_) => {
-                    println!("Document: {}", &file_str);
-                    Ok(())
-                }
-                Err(err) => {
-                    eprintln!("Error validating document against Serde structs: {}", err);
-                    Err(err.into())
+    let validator = validate::JSONSchemaValidator::<Value>::new(&schema)?;
+    let document_kind = validator.document_kind();
+
+    let batch_validator = |value: &Value| -> ReportResult {
+        validator.validate(value)?;
+        Ok(ValidationReport::new(document_kind))
+    };
+    validate_inputs(&sv.input, sv.output, batch_validator, |file_str| {
+        let document = serde_json::from_str::<serde_json::Value>(file_str)?;
+        let result = validator.validate(&document);
+
+        match result {
+            Ok(_) => {
+                println!("Valid document based on JSON schema");
+                match serde_json::from_value::<T>(document) {
+                    Ok(_) => {
+                        println!("Document: {}", file_str);
+                        Ok(())
+                    }
+                    Err(err) => {
+                        eprintln!("Error validating document against Serde structs: {}", err);
+                        Err(err.into())
+                    }
                 }
             }
+            Err(err) => {
+                eprintln!("Error validating document against JSON schema: {}", err);
+                Err(err.into())
+            }
         }
-        Err(err) => {
-            eprintln!("Error validating document against JSON schema: {}", err);
-            Err(err.into())
-        }
-    }
+    })
 }
 
 fn main() {
@@ -357,6 +1222,48 @@ fn main() {
                 process::exit(1);
             }
         }
+        Command::Verify(verify) => {
+            if let Err(e) = verify_cmd(verify) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Sign(sign) => {
+            if let Err(e) = sign_cmd(sign) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::VerifySubject(vs) => {
+            if let Err(e) = verify_subject_cmd(vs) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Convert(convert) => {
+            if let Err(e) = convert_cmd(convert) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::VerifyProvenance(vp) => {
+            if let Err(e) = verify_provenance_cmd(vp) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::SlsaLevel(sl) => {
+            if let Err(e) = slsa_level_cmd(sl) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Generate(generate) => {
+            if let Err(e) = generate_document_cmd(generate) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
         Command::CodeGenerate(cg) => {
             if let Err(e) = code_generate_cmd(cg) {
                 eprintln!("Error: {}", e);
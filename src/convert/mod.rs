@@ -0,0 +1,6 @@
+//! Conversion between versions of supply chain metadata documents.
+//!
+//! Conversions are lossless where the target version has an equivalent field. Information
+//! that can't be carried over is reported as warnings rather than silently dropped.
+
+pub mod slsa;
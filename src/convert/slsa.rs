@@ -0,0 +1,375 @@
+//! Conversion of SLSA Provenance v0.2 predicates to SLSA Provenance v1.
+//!
+//! Fields are mapped following the SLSA v0.2 to v1 migration guidance:
+//! `invocation.parameters` and `invocation.configSource` become external parameters,
+//! `invocation.environment` and `buildConfig` become internal parameters, and the config
+//! source and materials become resolved dependencies.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use url::Url;
+
+use crate::models::intoto::{
+    predicate::Predicate,
+    provenance::{
+        BuildDefinition, Builder, Metadata, ResourceDescriptor, RunDetails,
+        SLSAProvenanceV1Predicate, SLSA_PROVENANCE_V1_PREDICATE_TYPE,
+    },
+    provenance_v02::{SLSAProvenanceV02Predicate, SLSA_PROVENANCE_V02_PREDICATE_TYPE},
+    statement::{InTotoStatementV1, STATEMENT_TYPE_V1},
+};
+
+/// Converts a SLSA Provenance v0.2 predicate to a SLSA Provenance v1 predicate.
+///
+/// Returns the converted predicate along with warnings for fields that have no v1
+/// equivalent. The invocation ID and start time are required in v1 but optional in v0.2.
+/// When `metadata.buildInvocationId` is missing, `invocationId` is left empty. When
+/// `metadata.buildStartedOn` is missing, `startedOn` is taken from `metadata.buildFinishedOn`,
+/// or is the Unix epoch if that is missing too. Each of these fallbacks adds a warning.
+///
+/// Fails if the `buildType` is registered in `crate::models::intoto::build_type` and the
+/// converted parameters don't match its typed parameters, as the result wouldn't be valid
+/// SLSA Provenance v1.
+pub fn provenance_v02_to_v1(
+    predicate: SLSAProvenanceV02Predicate,
+) -> Result<(SLSAProvenanceV1Predicate, Vec<String>)> {
+    let mut warnings = Vec::new();
+    let mut external_parameters = Map::new();
+    let mut internal_parameters = Map::new();
+    let mut resolved_dependencies = Vec::new();
+
+    if let Some(invocation) = predicate.invocation {
+        if let Some(config_source) = invocation.config_source {
+            if let Some(uri) = &config_source.uri {
+                resolved_dependencies.push(ResourceDescriptor::new(
+                    Some(uri.clone().into()),
+                    config_source.digest.clone(),
+                ));
+            }
+            external_parameters.insert(
+                "configSource".to_string(),
+                serde_json::to_value(config_source)?,
+            );
+        }
+        if let Some(parameters) = invocation.parameters {
+            external_parameters.insert("parameters".to_string(), parameters);
+        }
+        if let Some(environment) = invocation.environment {
+            internal_parameters.insert("environment".to_string(), environment);
+        }
+    }
+    if let Some(build_config) = predicate.build_config {
+        internal_parameters.insert("buildConfig".to_string(), build_config);
+    }
+
+    for (index, material) in predicate
+        .materials
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+    {
+        if material.uri.is_none() && material.digest.is_none() {
+            warnings.push(format!(
+                "materials[{}] has neither a uri nor a digest, and was dropped",
+                index
+            ));
+            continue;
+        }
+        let descriptor = ResourceDescriptor::new(material.uri.map(Into::into), material.digest);
+        // The config source is usually also listed as a material.
+        if !resolved_dependencies.contains(&descriptor) {
+            resolved_dependencies.push(descriptor);
+        }
+    }
+
+    let metadata = predicate.metadata.unwrap_or_default();
+    if metadata.completeness.is_some() {
+        warnings.push(
+            "metadata.completeness has no SLSA Provenance v1 equivalent and was dropped"
+                .to_string(),
+        );
+    }
+    if metadata.reproducible.is_some() {
+        warnings.push(
+            "metadata.reproducible has no SLSA Provenance v1 equivalent and was dropped"
+                .to_string(),
+        );
+    }
+    let invocation_id = metadata.build_invocation_id.unwrap_or_else(|| {
+        warnings.push(
+            "metadata.buildInvocationId is missing, so runDetails.metadata.invocationId was \
+             left empty"
+                .to_string(),
+        );
+        String::new()
+    });
+    let started_on = match (metadata.build_started_on, metadata.build_finished_on) {
+        (Some(started_on), _) => started_on,
+        (None, Some(finished_on)) => {
+            warnings.push(
+                "metadata.buildStartedOn is missing, so runDetails.metadata.startedOn was set \
+                 to metadata.buildFinishedOn"
+                    .to_string(),
+            );
+            finished_on
+        }
+        (None, None) => {
+            warnings.push(
+                "metadata.buildStartedOn is missing, so runDetails.metadata.startedOn was set \
+                 to the Unix epoch"
+                    .to_string(),
+            );
+            DateTime::<Utc>::UNIX_EPOCH
+        }
+    };
+
+    let build_definition = BuildDefinition {
+        build_type: predicate.build_type,
+        external_parameters: Value::Object(external_parameters),
+        internal_parameters: Value::Object(internal_parameters),
+        resolved_dependencies,
+    };
+    build_definition.validate_parameters()?;
+
+    let converted = SLSAProvenanceV1Predicate {
+        build_definition,
+        run_details: RunDetails {
+            builder: Builder {
+                id: predicate.builder.id,
+                builder_dependencies: None,
+                version: None,
+            },
+            metadata: Metadata {
+                invocation_id,
+                started_on,
+                finished_on: metadata.build_finished_on,
+            },
+            byproducts: None,
+        },
+    };
+    Ok((converted, warnings))
+}
+
+/// Converts an In-Toto statement with a SLSA Provenance v0.2 predicate to an In-Toto v1
+/// statement with a SLSA Provenance v1 predicate.
+///
+/// The subjects are carried over unchanged. Returns the converted statement along with any
+/// warnings from converting the predicate.
+pub fn statement_v02_to_v1(
+    statement: InTotoStatementV1,
+) -> Result<(InTotoStatementV1, Vec<String>)> {
+    let predicate = match statement.predicate {
+        Predicate::SLSAProvenanceV02(predicate) => predicate,
+        _ => {
+            return Err(anyhow!(
+                "Unexpected predicateType: {:?}, expected {:?}",
+                statement.predicate_type.as_str(),
+                SLSA_PROVENANCE_V02_PREDICATE_TYPE
+            ))
+        }
+    };
+    let (predicate, warnings) = provenance_v02_to_v1(predicate)?;
+
+    let converted = InTotoStatementV1 {
+        _type: Url::parse(STATEMENT_TYPE_V1)?,
+        subject: statement.subject,
+        predicate_type: Url::parse(SLSA_PROVENANCE_V1_PREDICATE_TYPE)?,
+        predicate: Predicate::SLSAProvenanceV1(predicate),
+    };
+    Ok((converted, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::intoto::build_type;
+    use serde_json::json;
+
+    const STATEMENT_V02: &str = include_str!("../../tests/fixtures/slsa_provenance_v02.json");
+
+    fn statement_v02() -> InTotoStatementV1 {
+        let value = serde_json::from_str::<Value>(STATEMENT_V02).unwrap();
+        InTotoStatementV1::from_value_lenient(&value).unwrap().0
+    }
+
+    #[test]
+    fn convert_statement_v02_to_v1() {
+        let (converted, warnings) = statement_v02_to_v1(statement_v02()).unwrap();
+        assert_eq!(converted._type.as_str(), STATEMENT_TYPE_V1);
+        assert_eq!(
+            converted.predicate_type.as_str(),
+            SLSA_PROVENANCE_V1_PREDICATE_TYPE
+        );
+        assert_eq!(converted.subject.len(), 1);
+        assert_eq!(
+            warnings,
+            vec![
+                "metadata.completeness has no SLSA Provenance v1 equivalent and was dropped",
+                "metadata.reproducible has no SLSA Provenance v1 equivalent and was dropped",
+            ]
+        );
+
+        let predicate = match converted.predicate {
+            Predicate::SLSAProvenanceV1(predicate) => predicate,
+            other => panic!("Unexpected predicate: {:?}", other),
+        };
+        let build_definition = &predicate.build_definition;
+        assert_eq!(
+            build_definition.external_parameters["configSource"]["entryPoint"],
+            json!(".github/workflows/release.yml")
+        );
+        assert_eq!(
+            build_definition.internal_parameters["environment"]["github_event_name"],
+            json!("push")
+        );
+        // The config source material is deduplicated, leaving the config source and the
+        // builder image.
+        assert_eq!(build_definition.resolved_dependencies.len(), 2);
+        assert_eq!(predicate.run_details.metadata.invocation_id, "1536140711-1");
+        assert!(predicate.run_details.metadata.finished_on.is_some());
+
+        // The converted statement must round-trip as a strict v1 statement.
+        let json = serde_json::to_string(&InTotoStatementV1 {
+            predicate: Predicate::SLSAProvenanceV1(predicate),
+            ..converted
+        })
+        .unwrap();
+        assert!(serde_json::from_str::<InTotoStatementV1>(&json).is_ok());
+    }
+
+    #[test]
+    fn convert_in_toto_golang_statement() {
+        let value = serde_json::from_str::<Value>(include_str!(
+            "../../tests/fixtures/slsa_provenance_v02_in_toto_golang.json"
+        ))
+        .unwrap();
+        let statement = InTotoStatementV1::from_value_lenient(&value).unwrap().0;
+
+        let (converted, warnings) = statement_v02_to_v1(statement).unwrap();
+        assert_eq!(
+            warnings,
+            vec![
+                "metadata.completeness has no SLSA Provenance v1 equivalent and was dropped",
+                "metadata.reproducible has no SLSA Provenance v1 equivalent and was dropped",
+                "metadata.buildStartedOn is missing, so runDetails.metadata.startedOn was set to \
+                 the Unix epoch",
+            ]
+        );
+        let predicate = match converted.predicate {
+            Predicate::SLSAProvenanceV1(predicate) => predicate,
+            other => panic!("Unexpected predicate: {:?}", other),
+        };
+        assert_eq!(predicate.run_details.metadata.invocation_id, "1536140711-1");
+        assert_eq!(
+            predicate.run_details.metadata.started_on,
+            DateTime::<Utc>::UNIX_EPOCH
+        );
+    }
+
+    #[test]
+    fn convert_started_on_from_finished_on() {
+        let predicate = serde_json::from_value::<SLSAProvenanceV02Predicate>(json!({
+            "builder": {"id": "https://example.com/builder"},
+            "buildType": "https://example.com/buildType/v1",
+            "metadata": {
+                "buildInvocationId": "1",
+                "buildFinishedOn": "2023-01-01T12:44:56Z"
+            }
+        }))
+        .unwrap();
+
+        let (converted, warnings) = provenance_v02_to_v1(predicate).unwrap();
+        let metadata = &converted.run_details.metadata;
+        assert_eq!(metadata.started_on, metadata.finished_on.unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("set to metadata.buildFinishedOn"));
+    }
+
+    #[test]
+    fn convert_without_metadata() {
+        let predicate = serde_json::from_value::<SLSAProvenanceV02Predicate>(json!({
+            "builder": {"id": "https://example.com/builder"},
+            "buildType": "https://example.com/buildType/v1"
+        }))
+        .unwrap();
+
+        let (converted, warnings) = provenance_v02_to_v1(predicate).unwrap();
+        let metadata = &converted.run_details.metadata;
+        assert_eq!(metadata.invocation_id, "");
+        assert_eq!(metadata.started_on, DateTime::<Utc>::UNIX_EPOCH);
+        assert!(metadata.finished_on.is_none());
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("metadata.buildInvocationId is missing"));
+        assert!(warnings[1].starts_with("metadata.buildStartedOn is missing"));
+    }
+
+    #[test]
+    fn convert_materials_without_uri() {
+        let predicate = serde_json::from_value::<SLSAProvenanceV02Predicate>(json!({
+            "builder": {"id": "https://example.com/builder"},
+            "buildType": "https://example.com/buildType/v1",
+            "metadata": {
+                "buildInvocationId": "1",
+                "buildStartedOn": "2023-01-01T12:34:56Z"
+            },
+            "materials": [
+                {"digest": {"sha256": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"}},
+                {}
+            ]
+        }))
+        .unwrap();
+
+        let (converted, warnings) = provenance_v02_to_v1(predicate).unwrap();
+        let resolved_dependencies = &converted.build_definition.resolved_dependencies;
+        assert_eq!(resolved_dependencies.len(), 1);
+        assert!(resolved_dependencies[0].uri.is_none());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("materials[1] has neither a uri nor a digest"));
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    #[allow(dead_code)]
+    struct ConvertTestExternalParameters {
+        #[serde(rename = "configSource")]
+        config_source: Value,
+        parameters: Value,
+    }
+
+    #[test]
+    fn convert_registered_build_type() {
+        let predicate_with_build_type = |build_type: &str| {
+            let mut value =
+                serde_json::from_str::<Value>(STATEMENT_V02).unwrap()["predicate"].take();
+            value["buildType"] = json!(build_type);
+            serde_json::from_value::<SLSAProvenanceV02Predicate>(value).unwrap()
+        };
+
+        // The v0.2 invocation doesn't map onto the workflow build type's parameters.
+        let err = provenance_v02_to_v1(predicate_with_build_type(
+            build_type::GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE,
+        ))
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid externalParameters: unknown field `configSource`"));
+
+        let build_type = "https://example.com/buildType/convert-test/v1";
+        build_type::register::<ConvertTestExternalParameters, Value>(build_type, "ConvertTest")
+            .unwrap();
+        let (converted, _) = provenance_v02_to_v1(predicate_with_build_type(build_type)).unwrap();
+        assert_eq!(converted.build_definition.build_type.as_str(), build_type);
+    }
+
+    #[test]
+    fn convert_unexpected_predicate_type() {
+        let statement = serde_json::from_str::<InTotoStatementV1>(include_str!(
+            "../../tests/fixtures/slsa_provenance_v1.json"
+        ))
+        .unwrap();
+
+        let err = statement_v02_to_v1(statement).unwrap_err();
+        assert!(err.to_string().contains("Unexpected predicateType"));
+    }
+}
@@ -0,0 +1,196 @@
+//! Computing digests of local artifacts.
+//!
+//! Digests are computed for the algorithms of an In-Toto DigestSet, so that statement
+//! subjects can be compared against files on disk.
+
+use anyhow::{Context, Result};
+use sha2::digest::{DynDigest, ExtendableOutput, Update, VariableOutput};
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+
+use crate::models::intoto::statement::Algorithm;
+
+/// A request to compute a digest with a given algorithm.
+///
+/// `hex_len` is the length in hex characters of the expected digest. It selects the output
+/// length of variable-length algorithms (shake, blake2) and the object format of git blob IDs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DigestRequest {
+    pub algorithm: Algorithm,
+    pub hex_len: usize,
+}
+
+// A streaming hasher for a single digest request.
+enum Hasher {
+    Fixed(Box<dyn DynDigest>),
+    Shake128(sha3::Shake128, usize),
+    Shake256(sha3::Shake256, usize),
+    Blake2b(blake2::Blake2bVar),
+    Blake2s(blake2::Blake2sVar),
+}
+
+impl Hasher {
+    // Creates a hasher for the request, or None if the algorithm can't be computed from a file.
+    fn new(request: &DigestRequest, file_len: u64) -> Option<Self> {
+        let out_len = request.hex_len / 2;
+        let hasher = match request.algorithm {
+            Algorithm::Sha224 => Hasher::Fixed(Box::<sha2::Sha224>::default()),
+            Algorithm::Sha256 => Hasher::Fixed(Box::<sha2::Sha256>::default()),
+            Algorithm::Sha384 => Hasher::Fixed(Box::<sha2::Sha384>::default()),
+            Algorithm::Sha512 => Hasher::Fixed(Box::<sha2::Sha512>::default()),
+            Algorithm::Sha512_224 => Hasher::Fixed(Box::<sha2::Sha512_224>::default()),
+            Algorithm::Sha512_256 => Hasher::Fixed(Box::<sha2::Sha512_256>::default()),
+            Algorithm::Sha3_224 => Hasher::Fixed(Box::<sha3::Sha3_224>::default()),
+            Algorithm::Sha3_256 => Hasher::Fixed(Box::<sha3::Sha3_256>::default()),
+            Algorithm::Sha3_384 => Hasher::Fixed(Box::<sha3::Sha3_384>::default()),
+            Algorithm::Sha3_512 => Hasher::Fixed(Box::<sha3::Sha3_512>::default()),
+            Algorithm::Ripemd160 => Hasher::Fixed(Box::<ripemd::Ripemd160>::default()),
+            Algorithm::Sm3 => Hasher::Fixed(Box::<sm3::Sm3>::default()),
+            Algorithm::Sha1 => Hasher::Fixed(Box::<sha1::Sha1>::default()),
+            Algorithm::Md5 => Hasher::Fixed(Box::<md5::Md5>::default()),
+            Algorithm::Shake128 if out_len > 0 => Hasher::Shake128(Default::default(), out_len),
+            Algorithm::Shake256 if out_len > 0 => Hasher::Shake256(Default::default(), out_len),
+            Algorithm::Blake2b => Hasher::Blake2b(blake2::Blake2bVar::new(out_len).ok()?),
+            Algorithm::Blake2s => Hasher::Blake2s(blake2::Blake2sVar::new(out_len).ok()?),
+            // Git blob IDs hash the object header followed by the file contents.
+            Algorithm::GitBlob => {
+                let mut hasher: Box<dyn DynDigest> = match request.hex_len {
+                    40 => Box::<sha1::Sha1>::default(),
+                    64 => Box::<sha2::Sha256>::default(),
+                    _ => return None,
+                };
+                hasher.update(format!("blob {}\0", file_len).as_bytes());
+                Hasher::Fixed(hasher)
+            }
+            _ => return None,
+        };
+        Some(hasher)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Fixed(hasher) => DynDigest::update(hasher.as_mut(), data),
+            Hasher::Shake128(hasher, _) => Update::update(hasher, data),
+            Hasher::Shake256(hasher, _) => Update::update(hasher, data),
+            Hasher::Blake2b(hasher) => Update::update(hasher, data),
+            Hasher::Blake2s(hasher) => Update::update(hasher, data),
+        }
+    }
+
+    fn finalize(self) -> String {
+        let bytes = match self {
+            Hasher::Fixed(hasher) => hasher.finalize().to_vec(),
+            Hasher::Shake128(hasher, len) => hasher.finalize_boxed(len).to_vec(),
+            Hasher::Shake256(hasher, len) => hasher.finalize_boxed(len).to_vec(),
+            Hasher::Blake2b(hasher) => hasher.finalize_boxed().to_vec(),
+            Hasher::Blake2s(hasher) => hasher.finalize_boxed().to_vec(),
+        };
+        hex::encode(bytes)
+    }
+}
+
+/// Computes the hex-encoded digests of the file at `path` for the given requests.
+///
+/// The file is read once. Requests for algorithms that can't be computed from a single file,
+/// e.g. `gitCommit`, `dirHash` or custom algorithms, are omitted from the result.
+pub fn digest_file(
+    path: &Path,
+    requests: &[DigestRequest],
+) -> Result<HashMap<DigestRequest, String>> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let file_len = file.metadata()?.len();
+
+    let mut hashers = requests
+        .iter()
+        .filter_map(|request| Hasher::new(request, file_len).map(|h| (request.clone(), h)))
+        .collect::<Vec<_>>();
+
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        for (_, hasher) in hashers.iter_mut() {
+            hasher.update(&buffer[..read]);
+        }
+    }
+
+    Ok(hashers
+        .drain(..)
+        .map(|(request, hasher)| (request, hasher.finalize()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn request(algorithm: Algorithm, hex_len: usize) -> DigestRequest {
+        DigestRequest { algorithm, hex_len }
+    }
+
+    fn digest_hello(name: &str, requests: &[DigestRequest]) -> HashMap<DigestRequest, String> {
+        let dir = TempDir::new(&format!("digest_{}", name));
+        let path = dir.write("hello.txt", b"hello\n");
+        digest_file(&path, requests).unwrap()
+    }
+
+    #[test]
+    fn digest_file_fixed_algorithms() {
+        let sha256 = request(Algorithm::Sha256, 64);
+        let sha1 = request(Algorithm::Sha1, 40);
+        let md5 = request(Algorithm::Md5, 32);
+        let digests = digest_hello("fixed", &[sha256.clone(), sha1.clone(), md5.clone()]);
+
+        assert_eq!(
+            digests[&sha256],
+            "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03"
+        );
+        assert_eq!(digests[&sha1], "f572d396fae9206628714fb2ce00f72e94f2258f");
+        assert_eq!(digests[&md5], "b1946ac92492d2347c6235b4d2611184");
+    }
+
+    #[test]
+    fn digest_file_git_blob() {
+        let sha1_blob = request(Algorithm::GitBlob, 40);
+        let digests = digest_hello("git_blob", std::slice::from_ref(&sha1_blob));
+
+        // Matches `git hash-object` for a file containing "hello\n".
+        assert_eq!(
+            digests[&sha1_blob],
+            "ce013625030ba8dba906f756967f9e9ca394464a"
+        );
+    }
+
+    #[test]
+    fn digest_file_variable_length() {
+        let blake2b = request(Algorithm::Blake2b, 128);
+        let shake = request(Algorithm::Shake128, 64);
+        let digests = digest_hello("variable", &[blake2b.clone(), shake.clone()]);
+
+        assert_eq!(digests[&blake2b].len(), 128);
+        assert_eq!(digests[&shake].len(), 64);
+    }
+
+    #[test]
+    fn digest_file_unsupported_algorithms() {
+        let digests = digest_hello(
+            "unsupported",
+            &[
+                request(Algorithm::GitCommit, 40),
+                request(Algorithm::Custom("custom".to_string()), 8),
+            ],
+        );
+        assert!(digests.is_empty());
+    }
+
+    #[test]
+    fn digest_missing_file() {
+        let result = digest_file(Path::new("does/not/exist"), &[]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,253 @@
+//! Key models for signing and verifying signatures.
+//!
+//! This module provides the PublicKey enum, which can be loaded from a PEM-encoded
+//! SubjectPublicKeyInfo (or PKCS#1 for RSA) and used to verify raw signatures, and the
+//! PrivateKey enum, which can be loaded from a PEM-encoded PKCS#8 (or SEC1 for ECDSA)
+//! private key and used to produce them.
+
+use anyhow::{anyhow, Result};
+use p256::{
+    ecdsa::signature::{Signer, Verifier},
+    pkcs8::{DecodePrivateKey, DecodePublicKey},
+};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use sha2::Sha256;
+
+/// A public key used to verify signatures.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublicKey {
+    /// ECDSA over NIST P-256 with SHA-256.
+    EcdsaP256(p256::ecdsa::VerifyingKey),
+    /// Ed25519.
+    Ed25519(ed25519_dalek::VerifyingKey),
+    /// RSASSA-PSS with SHA-256.
+    RsaPss(rsa::RsaPublicKey),
+}
+
+impl PublicKey {
+    /// Parses a PEM-encoded public key.
+    ///
+    /// Supports `PUBLIC KEY` (SubjectPublicKeyInfo) blocks for all key types and
+    /// `RSA PUBLIC KEY` (PKCS#1) blocks for RSA keys.
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        if let Ok(key) = p256::ecdsa::VerifyingKey::from_public_key_pem(pem) {
+            return Ok(PublicKey::EcdsaP256(key));
+        }
+        if let Ok(key) = ed25519_dalek::VerifyingKey::from_public_key_pem(pem) {
+            return Ok(PublicKey::Ed25519(key));
+        }
+        if let Ok(key) = rsa::RsaPublicKey::from_public_key_pem(pem) {
+            return Ok(PublicKey::RsaPss(key));
+        }
+        if let Ok(key) = rsa::RsaPublicKey::from_pkcs1_pem(pem) {
+            return Ok(PublicKey::RsaPss(key));
+        }
+        Err(anyhow!(
+            "Unsupported public key: expected a PEM-encoded ECDSA P-256, Ed25519 or RSA public key"
+        ))
+    }
+
+    /// Returns a human readable name of the key's signature algorithm.
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            PublicKey::EcdsaP256(_) => "ECDSA P-256",
+            PublicKey::Ed25519(_) => "Ed25519",
+            PublicKey::RsaPss(_) => "RSA-PSS",
+        }
+    }
+
+    /// Verifies a signature over the given message.
+    ///
+    /// ECDSA signatures are accepted in either ASN.1 DER or fixed-size (r || s) encoding.
+    pub fn verify(&self, message: &[u8], sig: &[u8]) -> Result<()> {
+        match self {
+            PublicKey::EcdsaP256(key) => {
+                let signature = p256::ecdsa::Signature::from_der(sig)
+                    .or_else(|_| p256::ecdsa::Signature::from_slice(sig))
+                    .map_err(|e| anyhow!("Invalid ECDSA signature: {}", e))?;
+                key.verify(message, &signature)
+                    .map_err(|e| anyhow!("ECDSA signature verification failed: {}", e))
+            }
+            PublicKey::Ed25519(key) => {
+                let signature = ed25519_dalek::Signature::from_slice(sig)
+                    .map_err(|e| anyhow!("Invalid Ed25519 signature: {}", e))?;
+                key.verify(message, &signature)
+                    .map_err(|e| anyhow!("Ed25519 signature verification failed: {}", e))
+            }
+            PublicKey::RsaPss(key) => {
+                let verifying_key = rsa::pss::VerifyingKey::<Sha256>::new(key.clone());
+                let signature = rsa::pss::Signature::try_from(sig)
+                    .map_err(|e| anyhow!("Invalid RSA-PSS signature: {}", e))?;
+                verifying_key
+                    .verify(message, &signature)
+                    .map_err(|e| anyhow!("RSA-PSS signature verification failed: {}", e))
+            }
+        }
+    }
+}
+
+/// A private key used to sign messages.
+#[derive(Debug, Clone)]
+pub enum PrivateKey {
+    /// ECDSA over NIST P-256 with SHA-256.
+    EcdsaP256(p256::ecdsa::SigningKey),
+    /// Ed25519.
+    Ed25519(ed25519_dalek::SigningKey),
+}
+
+impl PrivateKey {
+    /// Parses a PEM-encoded private key.
+    ///
+    /// Supports unencrypted `PRIVATE KEY` (PKCS#8) blocks for all key types and
+    /// `EC PRIVATE KEY` (SEC1) blocks for ECDSA keys.
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        if let Ok(key) = p256::ecdsa::SigningKey::from_pkcs8_pem(pem) {
+            return Ok(PrivateKey::EcdsaP256(key));
+        }
+        if let Ok(key) = p256::SecretKey::from_sec1_pem(pem) {
+            return Ok(PrivateKey::EcdsaP256(key.into()));
+        }
+        if let Ok(key) = ed25519_dalek::SigningKey::from_pkcs8_pem(pem) {
+            return Ok(PrivateKey::Ed25519(key));
+        }
+        Err(anyhow!(
+            "Unsupported private key: expected an unencrypted PEM-encoded ECDSA P-256 or Ed25519 private key"
+        ))
+    }
+
+    /// Returns a human readable name of the key's signature algorithm.
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            PrivateKey::EcdsaP256(_) => "ECDSA P-256",
+            PrivateKey::Ed25519(_) => "Ed25519",
+        }
+    }
+
+    /// Returns the public key corresponding to this private key.
+    pub fn public_key(&self) -> PublicKey {
+        match self {
+            PrivateKey::EcdsaP256(key) => PublicKey::EcdsaP256(*key.verifying_key()),
+            PrivateKey::Ed25519(key) => PublicKey::Ed25519(key.verifying_key()),
+        }
+    }
+
+    /// Signs the given message.
+    ///
+    /// ECDSA signatures are produced in ASN.1 DER encoding.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        match self {
+            PrivateKey::EcdsaP256(key) => {
+                let signature: p256::ecdsa::Signature = key.sign(message);
+                signature.to_der().as_bytes().to_vec()
+            }
+            PrivateKey::Ed25519(key) => key.sign(message).to_bytes().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519_PUBLIC_KEY: &str = include_str!("../../tests/fixtures/keys/ed25519.pub.pem");
+    const ECDSA_P256_PUBLIC_KEY: &str =
+        include_str!("../../tests/fixtures/keys/ecdsa_p256.pub.pem");
+    const RSA_PUBLIC_KEY: &str = include_str!("../../tests/fixtures/keys/rsa.pub.pem");
+    const ED25519_PRIVATE_KEY: &str = include_str!("../../tests/fixtures/keys/ed25519.pem");
+    const ECDSA_P256_PRIVATE_KEY: &str = include_str!("../../tests/fixtures/keys/ecdsa_p256.pem");
+
+    #[test]
+    fn parse_public_keys() {
+        assert_eq!(
+            PublicKey::from_pem(ED25519_PUBLIC_KEY).unwrap().algorithm(),
+            "Ed25519"
+        );
+        assert_eq!(
+            PublicKey::from_pem(ECDSA_P256_PUBLIC_KEY)
+                .unwrap()
+                .algorithm(),
+            "ECDSA P-256"
+        );
+        assert_eq!(
+            PublicKey::from_pem(RSA_PUBLIC_KEY).unwrap().algorithm(),
+            "RSA-PSS"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_public_key() {
+        let result =
+            PublicKey::from_pem("-----BEGIN PUBLIC KEY-----\nAAAA\n-----END PUBLIC KEY-----\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn verify_ed25519_signature() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = PublicKey::Ed25519(signing_key.verifying_key());
+        let signature = signing_key.sign(b"message");
+
+        assert!(public_key.verify(b"message", &signature.to_bytes()).is_ok());
+        assert!(public_key
+            .verify(b"tampered", &signature.to_bytes())
+            .is_err());
+    }
+
+    #[test]
+    fn verify_ecdsa_p256_signature() {
+        let signing_key = p256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let public_key = PublicKey::EcdsaP256(*signing_key.verifying_key());
+        let signature: p256::ecdsa::Signature = signing_key.sign(b"message");
+
+        assert!(public_key
+            .verify(b"message", signature.to_der().as_bytes())
+            .is_ok());
+        assert!(public_key.verify(b"message", &signature.to_bytes()).is_ok());
+        assert!(public_key
+            .verify(b"tampered", signature.to_der().as_bytes())
+            .is_err());
+    }
+
+    #[test]
+    fn parse_private_keys_match_public_keys() {
+        let ed25519 = PrivateKey::from_pem(ED25519_PRIVATE_KEY).unwrap();
+        assert_eq!(ed25519.algorithm(), "Ed25519");
+        assert_eq!(
+            ed25519.public_key(),
+            PublicKey::from_pem(ED25519_PUBLIC_KEY).unwrap()
+        );
+
+        let ecdsa = PrivateKey::from_pem(ECDSA_P256_PRIVATE_KEY).unwrap();
+        assert_eq!(ecdsa.algorithm(), "ECDSA P-256");
+        assert_eq!(
+            ecdsa.public_key(),
+            PublicKey::from_pem(ECDSA_P256_PUBLIC_KEY).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_sec1_private_key() {
+        use p256::elliptic_curve::pkcs8::LineEnding;
+
+        let secret_key = p256::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let pem = secret_key.to_sec1_pem(LineEnding::LF).unwrap();
+        let key = PrivateKey::from_pem(&pem).unwrap();
+        assert_eq!(key.algorithm(), "ECDSA P-256");
+    }
+
+    #[test]
+    fn parse_unsupported_private_key() {
+        let result = PrivateKey::from_pem(RSA_PUBLIC_KEY);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        for pem in [ED25519_PRIVATE_KEY, ECDSA_P256_PRIVATE_KEY] {
+            let key = PrivateKey::from_pem(pem).unwrap();
+            let signature = key.sign(b"message");
+            assert!(key.public_key().verify(b"message", &signature).is_ok());
+            assert!(key.public_key().verify(b"tampered", &signature).is_err());
+        }
+    }
+}
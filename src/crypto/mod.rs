@@ -0,0 +1,7 @@
+//! Cryptographic primitives for signing, verifying and hashing supply chain metadata.
+//!
+//! Keys are loaded from PEM files and currently support ECDSA P-256, Ed25519 and RSA-PSS.
+//! Digests can be computed for most algorithms of an In-Toto DigestSet.
+
+pub mod digest;
+pub mod keys;
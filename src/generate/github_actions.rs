@@ -0,0 +1,184 @@
+//! Provenance for GitHub Actions workflows.
+//!
+//! Follows the GitHub Actions workflow build type,
+//! <https://slsa-framework.github.io/github-actions-buildtypes/workflow/v1>, which records
+//! the workflow as external parameters and the triggering event and runner environment as
+//! internal parameters.
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use super::required;
+use crate::models::intoto::{
+    build_type::GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE,
+    provenance_builder::{ProvenanceBuilder, ResourceDescriptorBuilder},
+    statement::Algorithm,
+};
+
+/// The build type of GitHub Actions workflows.
+pub const BUILD_TYPE: &str = GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE;
+
+/// Creates a provenance builder from the variables of a GitHub Actions job.
+pub fn provenance_builder(env: &HashMap<String, String>) -> Result<ProvenanceBuilder> {
+    let server_url = required(env, "GITHUB_SERVER_URL")?;
+    let repository = required(env, "GITHUB_REPOSITORY")?;
+    let git_ref = required(env, "GITHUB_REF")?;
+    let sha = required(env, "GITHUB_SHA")?;
+    let run_id = required(env, "GITHUB_RUN_ID")?;
+    let run_attempt = required(env, "GITHUB_RUN_ATTEMPT")?;
+    let event_name = required(env, "GITHUB_EVENT_NAME")?;
+
+    // GITHUB_WORKFLOW_REF has the form `{owner}/{repo}/{path}@{ref}`.
+    let workflow_ref = required(env, "GITHUB_WORKFLOW_REF")?;
+    let (workflow_path, workflow_git_ref) = workflow_ref
+        .strip_prefix(&format!("{}/", repository))
+        .and_then(|rest| rest.split_once('@'))
+        .ok_or_else(|| anyhow!("Invalid GITHUB_WORKFLOW_REF {:?}", workflow_ref))?;
+
+    // Runners are GitHub-hosted unless reported otherwise.
+    let runner = match env.get("RUNNER_ENVIRONMENT").map(|s| s.as_str()) {
+        Some("self-hosted") => "self-hosted",
+        _ => "github-hosted",
+    };
+
+    let mut github = Map::new();
+    github.insert("event_name".to_string(), json!(event_name));
+    for (key, name) in [
+        ("repository_id", "GITHUB_REPOSITORY_ID"),
+        ("repository_owner_id", "GITHUB_REPOSITORY_OWNER_ID"),
+        ("runner_environment", "RUNNER_ENVIRONMENT"),
+    ] {
+        if let Some(value) = env.get(name).filter(|value| !value.is_empty()) {
+            github.insert(key.to_string(), json!(value));
+        }
+    }
+
+    let source =
+        ResourceDescriptorBuilder::new(format!("git+{}/{}@{}", server_url, repository, git_ref))
+            .digest(Algorithm::GitCommit, sha)
+            .build()?;
+
+    Ok(ProvenanceBuilder::new(BUILD_TYPE)
+        .external_param(
+            "workflow",
+            json!({
+                "ref": workflow_git_ref,
+                "repository": format!("{}/{}", server_url, repository),
+                "path": workflow_path,
+            }),
+        )
+        .internal_param("github", Value::Object(github))
+        .resolved_dependency(source)
+        .builder_id(format!("https://github.com/actions/runner/{}", runner))
+        .invocation_id(format!(
+            "{}/{}/actions/runs/{}/attempts/{}",
+            server_url, repository, run_id, run_attempt
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::intoto::provenance::SLSAProvenanceV1Predicate;
+
+    fn github_env() -> HashMap<String, String> {
+        [
+            ("GITHUB_SERVER_URL", "https://github.com"),
+            ("GITHUB_REPOSITORY", "octocat/hello-world"),
+            ("GITHUB_REPOSITORY_ID", "1296269"),
+            ("GITHUB_REPOSITORY_OWNER_ID", "583231"),
+            ("GITHUB_REF", "refs/heads/main"),
+            ("GITHUB_SHA", "c27d339ee6075c1f744c5d4b200f7901aad2c369"),
+            ("GITHUB_RUN_ID", "1536140711"),
+            ("GITHUB_RUN_ATTEMPT", "1"),
+            ("GITHUB_EVENT_NAME", "workflow_dispatch"),
+            (
+                "GITHUB_WORKFLOW_REF",
+                "octocat/hello-world/.github/workflows/release.yml@refs/heads/main",
+            ),
+            ("RUNNER_ENVIRONMENT", "github-hosted"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+    }
+
+    fn build(env: &HashMap<String, String>) -> SLSAProvenanceV1Predicate {
+        provenance_builder(env)
+            .unwrap()
+            .started_now()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn github_actions_provenance() {
+        let provenance = build(&github_env());
+        let build_definition = &provenance.build_definition;
+
+        assert_eq!(build_definition.build_type.as_str(), BUILD_TYPE);
+        assert_eq!(
+            build_definition.external_parameters,
+            json!({
+                "workflow": {
+                    "ref": "refs/heads/main",
+                    "repository": "https://github.com/octocat/hello-world",
+                    "path": ".github/workflows/release.yml"
+                }
+            })
+        );
+        assert_eq!(
+            build_definition.internal_parameters["github"]["event_name"],
+            json!("workflow_dispatch")
+        );
+        assert_eq!(
+            build_definition.resolved_dependencies[0]
+                .uri
+                .as_ref()
+                .unwrap()
+                .to_string(),
+            "git+https://github.com/octocat/hello-world@refs/heads/main"
+        );
+        assert_eq!(
+            provenance.run_details.builder.id.as_str(),
+            "https://github.com/actions/runner/github-hosted"
+        );
+        assert_eq!(
+            provenance.run_details.metadata.invocation_id,
+            "https://github.com/octocat/hello-world/actions/runs/1536140711/attempts/1"
+        );
+    }
+
+    #[test]
+    fn github_actions_self_hosted_runner() {
+        let mut env = github_env();
+        env.insert("RUNNER_ENVIRONMENT".to_string(), "self-hosted".to_string());
+
+        let provenance = build(&env);
+        assert_eq!(
+            provenance.run_details.builder.id.as_str(),
+            "https://github.com/actions/runner/self-hosted"
+        );
+    }
+
+    #[test]
+    fn github_actions_missing_variable() {
+        let mut env = github_env();
+        env.remove("GITHUB_SHA");
+
+        let err = provenance_builder(&env).unwrap_err();
+        assert_eq!(err.to_string(), "Missing environment variable GITHUB_SHA");
+    }
+
+    #[test]
+    fn github_actions_invalid_workflow_ref() {
+        let mut env = github_env();
+        env.insert(
+            "GITHUB_WORKFLOW_REF".to_string(),
+            "other/repo/.github/workflows/release.yml@refs/heads/main".to_string(),
+        );
+
+        assert!(provenance_builder(&env).is_err());
+    }
+}
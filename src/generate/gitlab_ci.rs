@@ -0,0 +1,134 @@
+//! Provenance for GitLab CI jobs.
+//!
+//! Follows the build type of the provenance generated by GitLab Runner, recording the
+//! project, ref, job and pipeline source as external parameters and the pipeline and runner
+//! as internal parameters.
+
+use anyhow::Result;
+use serde_json::json;
+use std::collections::HashMap;
+
+use super::required;
+use crate::models::intoto::{
+    provenance_builder::{ProvenanceBuilder, ResourceDescriptorBuilder},
+    statement::Algorithm,
+};
+
+/// Creates a provenance builder from the variables of a GitLab CI job.
+pub fn provenance_builder(env: &HashMap<String, String>) -> Result<ProvenanceBuilder> {
+    let server_url = required(env, "CI_SERVER_URL")?;
+    let project_url = required(env, "CI_PROJECT_URL")?;
+    let project_path = required(env, "CI_PROJECT_PATH")?;
+    let git_ref = required(env, "CI_COMMIT_REF_NAME")?;
+    let sha = required(env, "CI_COMMIT_SHA")?;
+    let job_name = required(env, "CI_JOB_NAME")?;
+    let job_url = required(env, "CI_JOB_URL")?;
+    let pipeline_source = required(env, "CI_PIPELINE_SOURCE")?;
+    let runner_id = required(env, "CI_RUNNER_ID")?;
+    let runner_version = required(env, "CI_RUNNER_VERSION")?;
+
+    let optional = |name: &str| env.get(name).cloned().unwrap_or_default();
+
+    let source = ResourceDescriptorBuilder::new(format!("git+{}@{}", project_url, git_ref))
+        .digest(Algorithm::GitCommit, sha)
+        .build()?;
+
+    Ok(ProvenanceBuilder::new(format!(
+        "https://gitlab.com/gitlab-org/gitlab-runner/-/blob/v{}/PROVENANCE.md",
+        runner_version
+    ))
+    .external_param("source", project_url)
+    .external_param("ref", git_ref)
+    .external_param("entry_point", job_name)
+    .external_param("pipeline_source", pipeline_source)
+    .internal_param(
+        "gitlab",
+        json!({
+            "project_id": optional("CI_PROJECT_ID"),
+            "pipeline_id": optional("CI_PIPELINE_ID"),
+            "job_id": optional("CI_JOB_ID"),
+            "runner_description": optional("CI_RUNNER_DESCRIPTION"),
+        }),
+    )
+    .resolved_dependency(source)
+    .builder_id(format!(
+        "{}/{}/-/runners/{}",
+        server_url, project_path, runner_id
+    ))
+    .builder_version(runner_version)
+    .invocation_id(job_url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gitlab_env() -> HashMap<String, String> {
+        [
+            ("CI_SERVER_URL", "https://gitlab.com"),
+            ("CI_PROJECT_URL", "https://gitlab.com/octocat/hello-world"),
+            ("CI_PROJECT_PATH", "octocat/hello-world"),
+            ("CI_PROJECT_ID", "1234"),
+            ("CI_COMMIT_REF_NAME", "main"),
+            ("CI_COMMIT_SHA", "c27d339ee6075c1f744c5d4b200f7901aad2c369"),
+            ("CI_JOB_NAME", "release"),
+            ("CI_JOB_ID", "5678"),
+            (
+                "CI_JOB_URL",
+                "https://gitlab.com/octocat/hello-world/-/jobs/5678",
+            ),
+            ("CI_PIPELINE_ID", "91011"),
+            ("CI_PIPELINE_SOURCE", "push"),
+            ("CI_RUNNER_ID", "12"),
+            ("CI_RUNNER_VERSION", "16.5.0"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn gitlab_ci_provenance() {
+        let provenance = provenance_builder(&gitlab_env())
+            .unwrap()
+            .started_now()
+            .build()
+            .unwrap();
+        let build_definition = &provenance.build_definition;
+
+        assert_eq!(
+            build_definition.build_type.as_str(),
+            "https://gitlab.com/gitlab-org/gitlab-runner/-/blob/v16.5.0/PROVENANCE.md"
+        );
+        assert_eq!(
+            build_definition.external_parameters,
+            json!({
+                "source": "https://gitlab.com/octocat/hello-world",
+                "ref": "main",
+                "entry_point": "release",
+                "pipeline_source": "push"
+            })
+        );
+        assert_eq!(
+            build_definition.internal_parameters["gitlab"]["pipeline_id"],
+            json!("91011")
+        );
+        assert_eq!(
+            provenance.run_details.builder.id.as_str(),
+            "https://gitlab.com/octocat/hello-world/-/runners/12"
+        );
+        assert_eq!(
+            provenance.run_details.metadata.invocation_id,
+            "https://gitlab.com/octocat/hello-world/-/jobs/5678"
+        );
+    }
+
+    #[test]
+    fn gitlab_ci_missing_variable() {
+        let mut env = gitlab_env();
+        env.remove("CI_JOB_URL");
+
+        let err = provenance_builder(&env).unwrap_err();
+        assert_eq!(err.to_string(), "Missing environment variable CI_JOB_URL");
+    }
+}
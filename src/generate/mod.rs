@@ -0,0 +1,68 @@
+//! Generation of supply chain metadata documents.
+//!
+//! Provenance is generated from a snapshot of a CI environment's variables, so it can be
+//! produced inside a CI job without network calls, and tested by setting the variables
+//! locally.
+
+use anyhow::{anyhow, Result};
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::{
+    crypto::digest::{digest_file, DigestRequest},
+    models::intoto::statement::{Algorithm, DigestSet, InTotoStatementV1},
+};
+
+pub mod github_actions;
+pub mod gitlab_ci;
+
+/// A CI environment provenance can be generated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiEnvironment {
+    GithubActions,
+    GitlabCi,
+}
+
+/// Generates a SLSA Provenance v1 statement from a CI environment's variables.
+///
+/// The subjects are the given artifacts, named by their file name, with their SHA-256
+/// digests. The build is recorded as started when the provenance is generated.
+pub fn generate_provenance(
+    ci: CiEnvironment,
+    env: &HashMap<String, String>,
+    artifacts: &[PathBuf],
+) -> Result<InTotoStatementV1> {
+    let mut builder = match ci {
+        CiEnvironment::GithubActions => github_actions::provenance_builder(env)?,
+        CiEnvironment::GitlabCi => gitlab_ci::provenance_builder(env)?,
+    }
+    .started_now();
+
+    let request = DigestRequest {
+        algorithm: Algorithm::Sha256,
+        hex_len: 64,
+    };
+    for artifact in artifacts {
+        let mut digests = digest_file(artifact, std::slice::from_ref(&request))?;
+        let digest = digests
+            .remove(&request)
+            .ok_or_else(|| anyhow!("Failed to digest {}", artifact.display()))?;
+        let name = artifact
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| artifact.display().to_string());
+        builder = builder.subject(
+            name,
+            DigestSet::new(HashMap::from([(Algorithm::Sha256, digest)]))?,
+        );
+    }
+
+    builder.build_statement()
+}
+
+// Returns the value of a required environment variable.
+fn required<'a>(env: &'a HashMap<String, String>, name: &str) -> Result<&'a str> {
+    env.get(name)
+        .map(|value| value.as_str())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| anyhow!("Missing environment variable {}", name))
+}
@@ -0,0 +1,180 @@
+//! Resolving the documents to read from command line arguments.
+//!
+//! An argument is either `-` for stdin, a file, a directory whose JSON files are read
+//! recursively, or a glob pattern such as `sboms/**/*.json`, so that patterns work even when
+//! the shell doesn't expand them.
+
+use anyhow::{anyhow, Context, Result};
+use std::{
+    collections::HashSet,
+    fmt,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// The argument standing for stdin.
+pub const STDIN: &str = "-";
+
+/// A document to read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    /// Reads the whole document.
+    pub fn read_to_string(&self) -> Result<String> {
+        match self {
+            Input::Stdin => {
+                let mut contents = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut contents)
+                    .context("Failed to read stdin")?;
+                Ok(contents)
+            }
+            Input::File(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+}
+
+impl From<&Path> for Input {
+    fn from(path: &Path) -> Self {
+        if path.as_os_str() == STDIN {
+            Input::Stdin
+        } else {
+            Input::File(path.to_path_buf())
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Stdin => f.write_str(STDIN),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Expands arguments into the documents to read, in order and without duplicates.
+///
+/// Directories are walked recursively for files with a `.json` extension, sorted by path.
+/// Glob patterns must match at least one file.
+pub fn expand_inputs<P: AsRef<Path>>(args: &[P]) -> Result<Vec<Input>> {
+    let mut inputs = Vec::new();
+    let mut seen = HashSet::new();
+    for arg in args {
+        let path = arg.as_ref();
+        let pattern = path.to_string_lossy();
+        let expanded = if path.as_os_str() == STDIN {
+            vec![Input::Stdin]
+        } else if path.is_dir() {
+            let mut files = Vec::new();
+            walk_json_files(path, &mut files)?;
+            files.sort();
+            files.into_iter().map(Input::File).collect()
+        } else if !path.exists() && pattern.contains(['*', '?', '[']) {
+            let files = glob::glob(&pattern)
+                .map_err(|e| anyhow!("Invalid glob pattern {:?}: {}", pattern, e))?
+                .filter_map(|entry| entry.ok())
+                .filter(|path| path.is_file())
+                .map(Input::File)
+                .collect::<Vec<_>>();
+            if files.is_empty() {
+                return Err(anyhow!("No files match {:?}", pattern));
+            }
+            files
+        } else {
+            vec![Input::File(path.to_path_buf())]
+        };
+        for input in expanded {
+            if seen.insert(input.clone()) {
+                inputs.push(input);
+            }
+        }
+    }
+    Ok(inputs)
+}
+
+// Collects the JSON files under a directory.
+fn walk_json_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            walk_json_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn write_tree(test: &str) -> TempDir {
+        let dir = TempDir::new(test);
+        for name in ["b.json", "a.json", "notes.txt", "nested/c.json"] {
+            dir.write(name, b"{}");
+        }
+        dir
+    }
+
+    #[test]
+    fn expand_directory() {
+        let tree = write_tree("input_expand_directory");
+        let dir = tree.path();
+        let inputs = expand_inputs(&[dir]).unwrap();
+        assert_eq!(
+            inputs,
+            vec![
+                Input::File(dir.join("a.json")),
+                Input::File(dir.join("b.json")),
+                Input::File(dir.join("nested/c.json")),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_glob_stdin_and_duplicates() {
+        let tree = write_tree("input_expand_glob");
+        let dir = tree.path();
+        let pattern = dir.join("**/*.json");
+        let inputs = expand_inputs(&[
+            PathBuf::from(STDIN),
+            dir.join("b.json"),
+            pattern,
+            PathBuf::from(STDIN),
+        ])
+        .unwrap();
+        assert_eq!(
+            inputs,
+            vec![
+                Input::Stdin,
+                Input::File(dir.join("b.json")),
+                Input::File(dir.join("a.json")),
+                Input::File(dir.join("nested/c.json")),
+            ]
+        );
+        assert_eq!(inputs[0].to_string(), "-");
+    }
+
+    #[test]
+    fn expand_unmatched_glob() {
+        let tree = write_tree("input_unmatched_glob");
+        let dir = tree.path();
+        let err = expand_inputs(&[dir.join("*.yaml")]).unwrap_err();
+        assert!(err.to_string().starts_with("No files match"));
+
+        // Plain paths are kept, and fail when read.
+        let missing = dir.join("missing.json");
+        let inputs = expand_inputs(&[&missing]).unwrap();
+        assert!(inputs[0].read_to_string().is_err());
+    }
+}
@@ -1,2 +1,11 @@
+pub mod convert;
+pub mod crypto;
+pub mod generate;
+pub mod input;
+pub mod license;
 pub mod models;
+pub mod sign;
+#[cfg(test)]
+mod test_util;
 pub mod validate;
+pub mod verify;
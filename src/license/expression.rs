@@ -0,0 +1,488 @@
+//! Parsing SPDX license expressions.
+//!
+//! Expressions follow Annex D of the SPDX 2.3 specification: licenses combined with `AND`,
+//! `OR` and `WITH`, in decreasing order of precedence `WITH`, `AND`, `OR`, and grouped with
+//! parentheses. Operators are case-sensitive. `NONE` and `NOASSERTION` are only allowed as
+//! a whole expression.
+
+use anyhow::{anyhow, Result};
+use std::{fmt, str::FromStr};
+
+const LICENSE_REF_PREFIX: &str = "LicenseRef-";
+const DOCUMENT_REF_PREFIX: &str = "DocumentRef-";
+
+/// The maximum nesting of parentheses in an expression, so that hostile expressions can't
+/// exhaust the stack.
+pub const MAX_DEPTH: usize = 64;
+
+/// A parsed SPDX license expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseExpression {
+    /// `NONE`: no license applies.
+    None,
+    /// `NOASSERTION`: the license wasn't determined.
+    NoAssertion,
+    Term(LicenseTerm),
+    /// Two or more expressions combined with `AND`.
+    And(Vec<LicenseExpression>),
+    /// Two or more expressions combined with `OR`.
+    Or(Vec<LicenseExpression>),
+}
+
+/// A license, with an optional exception given with `WITH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseTerm {
+    pub license: SimpleLicense,
+    pub exception: Option<String>,
+}
+
+/// A license of the SPDX License List or a reference to a license defined elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimpleLicense {
+    /// A license identifier such as `MIT`, with `+` for any later version.
+    Id { id: String, or_later: bool },
+    /// A `LicenseRef-`, optionally defined in an external document given by a `DocumentRef-`.
+    Ref {
+        document_ref: Option<String>,
+        license_ref: String,
+    },
+}
+
+impl LicenseExpression {
+    /// Parses a license expression.
+    pub fn parse(expression: &str) -> Result<Self> {
+        let tokens = tokenize(expression)?;
+        if let [Token::Word(word, _)] = tokens[..] {
+            match word {
+                "NONE" => return Ok(LicenseExpression::None),
+                "NOASSERTION" => return Ok(LicenseExpression::NoAssertion),
+                _ => {}
+            }
+        }
+        let mut parser = Parser {
+            expression,
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let parsed = parser.or_expression()?;
+        match parser.peek() {
+            None => Ok(parsed),
+            Some(token) => Err(parser.error("AND, OR or WITH", Some(token))),
+        }
+    }
+
+    /// Returns the terms of the expression, from left to right.
+    pub fn terms(&self) -> Vec<&LicenseTerm> {
+        match self {
+            LicenseExpression::None | LicenseExpression::NoAssertion => Vec::new(),
+            LicenseExpression::Term(term) => vec![term],
+            LicenseExpression::And(operands) | LicenseExpression::Or(operands) => operands
+                .iter()
+                .flat_map(|operand| operand.terms())
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for LicenseExpression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        LicenseExpression::parse(s)
+    }
+}
+
+impl fmt::Display for LicenseExpression {
+    /// Formats the expression with the parentheses needed to keep its meaning.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LicenseExpression::None => f.write_str("NONE"),
+            LicenseExpression::NoAssertion => f.write_str("NOASSERTION"),
+            LicenseExpression::Term(term) => write!(f, "{}", term),
+            LicenseExpression::And(operands) | LicenseExpression::Or(operands) => {
+                let and = matches!(self, LicenseExpression::And(_));
+                for (i, operand) in operands.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(if and { " AND " } else { " OR " })?;
+                    }
+                    // Nested expressions are parenthesized, even where precedence allows
+                    // leaving them out.
+                    match operand {
+                        LicenseExpression::And(_) | LicenseExpression::Or(_) => {
+                            write!(f, "({})", operand)?
+                        }
+                        _ => write!(f, "{}", operand)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for LicenseTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.license)?;
+        if let Some(exception) = &self.exception {
+            write!(f, " WITH {}", exception)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SimpleLicense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimpleLicense::Id { id, or_later } => {
+                write!(f, "{}{}", id, if *or_later { "+" } else { "" })
+            }
+            SimpleLicense::Ref {
+                document_ref: Some(document_ref),
+                license_ref,
+            } => write!(f, "{}:{}", document_ref, license_ref),
+            SimpleLicense::Ref { license_ref, .. } => f.write_str(license_ref),
+        }
+    }
+}
+
+// A token of an expression with its byte offset.
+#[derive(Debug, Clone, Copy)]
+enum Token<'a> {
+    Open(usize),
+    Close(usize),
+    Word(&'a str, usize),
+}
+
+impl Token<'_> {
+    fn offset(&self) -> usize {
+        match self {
+            Token::Open(offset) | Token::Close(offset) | Token::Word(_, offset) => *offset,
+        }
+    }
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Open(_) => f.write_str("\"(\""),
+            Token::Close(_) => f.write_str("\")\""),
+            Token::Word(word, _) => write!(f, "{:?}", word),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in expression.char_indices() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if let Some(start) = start.take() {
+                tokens.push(Token::Word(&expression[start..i], start));
+            }
+            match c {
+                '(' => tokens.push(Token::Open(i)),
+                ')' => tokens.push(Token::Close(i)),
+                _ => {}
+            }
+        } else if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+' | ':') {
+            start.get_or_insert(i);
+        } else {
+            return Err(anyhow!(
+                "Invalid license expression {:?}: unexpected {:?} at position {}",
+                expression,
+                c,
+                i + 1
+            ));
+        }
+    }
+    if let Some(start) = start {
+        tokens.push(Token::Word(&expression[start..], start));
+    }
+    if tokens.is_empty() {
+        return Err(anyhow!("Invalid license expression: empty expression"));
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    expression: &'a str,
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+    // The number of enclosing parentheses.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.peek();
+        self.pos += 1;
+        token
+    }
+
+    // Consumes the next token if it is the given operator.
+    fn operator(&mut self, operator: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Word(word, _)) if word == operator);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or_expression(&mut self) -> Result<LicenseExpression> {
+        let mut operands = vec![self.and_expression()?];
+        while self.operator("OR") {
+            operands.push(self.and_expression()?);
+        }
+        Ok(match operands.len() {
+            1 => operands.remove(0),
+            _ => LicenseExpression::Or(operands),
+        })
+    }
+
+    fn and_expression(&mut self) -> Result<LicenseExpression> {
+        let mut operands = vec![self.primary()?];
+        while self.operator("AND") {
+            operands.push(self.primary()?);
+        }
+        Ok(match operands.len() {
+            1 => operands.remove(0),
+            _ => LicenseExpression::And(operands),
+        })
+    }
+
+    fn primary(&mut self) -> Result<LicenseExpression> {
+        match self.next() {
+            Some(Token::Open(offset)) => {
+                if self.depth == MAX_DEPTH {
+                    return Err(anyhow!(
+                        "Invalid license expression {:?}: more than {} nested parentheses at \
+                         position {}",
+                        self.expression,
+                        MAX_DEPTH,
+                        offset + 1
+                    ));
+                }
+                self.depth += 1;
+                let expression = self.or_expression()?;
+                self.depth -= 1;
+                match self.next() {
+                    Some(Token::Close(_)) => Ok(expression),
+                    token => Err(self.error("AND, OR or \")\"", token)),
+                }
+            }
+            Some(Token::Word(word, offset)) => {
+                let license = self.simple_license(word, offset)?;
+                let exception = match self.operator("WITH") {
+                    true => match self.next() {
+                        Some(Token::Word(exception, _)) if is_idstring(exception) => {
+                            Some(exception.to_string())
+                        }
+                        token => return Err(self.error("a license exception", token)),
+                    },
+                    false => None,
+                };
+                Ok(LicenseExpression::Term(LicenseTerm { license, exception }))
+            }
+            token => Err(self.error("a license", token)),
+        }
+    }
+
+    fn simple_license(&self, word: &str, offset: usize) -> Result<SimpleLicense> {
+        let invalid = || self.error("a license", Some(Token::Word(word, offset)));
+        if matches!(word, "AND" | "OR" | "WITH" | "NONE" | "NOASSERTION") {
+            return Err(invalid());
+        }
+        if let Some((document_ref, license_ref)) = word.split_once(':') {
+            return match is_ref(document_ref, DOCUMENT_REF_PREFIX)
+                && is_ref(license_ref, LICENSE_REF_PREFIX)
+            {
+                true => Ok(SimpleLicense::Ref {
+                    document_ref: Some(document_ref.to_string()),
+                    license_ref: license_ref.to_string(),
+                }),
+                false => Err(invalid()),
+            };
+        }
+        if word.starts_with(LICENSE_REF_PREFIX) {
+            return match is_ref(word, LICENSE_REF_PREFIX) {
+                true => Ok(SimpleLicense::Ref {
+                    document_ref: None,
+                    license_ref: word.to_string(),
+                }),
+                false => Err(invalid()),
+            };
+        }
+        let (id, or_later) = match word.strip_suffix('+') {
+            Some(id) => (id, true),
+            None => (word, false),
+        };
+        match is_idstring(id) {
+            true => Ok(SimpleLicense::Id {
+                id: id.to_string(),
+                or_later,
+            }),
+            false => Err(invalid()),
+        }
+    }
+
+    fn error(&self, expected: &str, found: Option<Token>) -> anyhow::Error {
+        match found {
+            Some(token) => anyhow!(
+                "Invalid license expression {:?}: expected {}, found {} at position {}",
+                self.expression,
+                expected,
+                token,
+                token.offset() + 1
+            ),
+            None => anyhow!(
+                "Invalid license expression {:?}: expected {}, found the end of the expression",
+                self.expression,
+                expected
+            ),
+        }
+    }
+}
+
+// Returns true if a string is a non-empty SPDX idstring, made of letters, numbers, . and -.
+fn is_idstring(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+}
+
+fn is_ref(s: &str, prefix: &str) -> bool {
+    s.strip_prefix(prefix).is_some_and(is_idstring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(id: &str) -> LicenseExpression {
+        LicenseExpression::Term(LicenseTerm {
+            license: SimpleLicense::Id {
+                id: id.to_string(),
+                or_later: false,
+            },
+            exception: None,
+        })
+    }
+
+    #[test]
+    fn parse_precedence() {
+        let expression = LicenseExpression::parse("MIT OR Apache-2.0 AND BSD-3-Clause").unwrap();
+        assert_eq!(
+            expression,
+            LicenseExpression::Or(vec![
+                id("MIT"),
+                LicenseExpression::And(vec![id("Apache-2.0"), id("BSD-3-Clause")]),
+            ])
+        );
+        assert_eq!(
+            expression.to_string(),
+            "MIT OR (Apache-2.0 AND BSD-3-Clause)"
+        );
+
+        let expression = "(MIT OR Apache-2.0) AND BSD-3-Clause"
+            .parse::<LicenseExpression>()
+            .unwrap();
+        assert!(matches!(expression, LicenseExpression::And(..)));
+        assert_eq!(
+            expression.to_string(),
+            "(MIT OR Apache-2.0) AND BSD-3-Clause"
+        );
+    }
+
+    #[test]
+    fn parse_deep_expressions() {
+        let nested = |depth: usize| format!("{}MIT{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(
+            LicenseExpression::parse(&nested(MAX_DEPTH)).unwrap(),
+            id("MIT")
+        );
+        let err = LicenseExpression::parse(&nested(200_000)).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("more than 64 nested parentheses at position 65"));
+
+        // Long chains of operators don't nest.
+        let chain = vec!["MIT"; 200_000].join(" AND ");
+        let expression = LicenseExpression::parse(&chain).unwrap();
+        assert_eq!(expression.terms().len(), 200_000);
+    }
+
+    #[test]
+    fn parse_terms() {
+        let expression = LicenseExpression::parse(
+            "GPL-2.0-or-later WITH Classpath-exception-2.0 OR (LGPL-2.1+ AND \
+             DocumentRef-spdx-tool-1.2:LicenseRef-MIT-Style-2 AND LicenseRef-1)",
+        )
+        .unwrap();
+        let terms = expression.terms();
+        assert_eq!(terms.len(), 4);
+        assert_eq!(
+            terms[0].exception.as_deref(),
+            Some("Classpath-exception-2.0")
+        );
+        assert_eq!(
+            terms[1].license,
+            SimpleLicense::Id {
+                id: "LGPL-2.1".to_string(),
+                or_later: true
+            }
+        );
+        assert_eq!(
+            terms[2].license,
+            SimpleLicense::Ref {
+                document_ref: Some("DocumentRef-spdx-tool-1.2".to_string()),
+                license_ref: "LicenseRef-MIT-Style-2".to_string()
+            }
+        );
+        assert_eq!(terms[3].to_string(), "LicenseRef-1");
+
+        assert_eq!(
+            LicenseExpression::parse(" NOASSERTION ").unwrap(),
+            LicenseExpression::NoAssertion
+        );
+        assert!(LicenseExpression::parse("NONE").unwrap().terms().is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cases = [
+            ("", "empty expression"),
+            (
+                "MIT OR",
+                "expected a license, found the end of the expression",
+            ),
+            (
+                "MIT or Apache-2.0",
+                "expected AND, OR or WITH, found \"or\" at position 5",
+            ),
+            (
+                "(MIT OR Apache-2.0",
+                "expected AND, OR or \")\", found the end",
+            ),
+            (
+                "MIT AND NONE",
+                "expected a license, found \"NONE\" at position 9",
+            ),
+            ("MIT WITH", "expected a license exception"),
+            ("LicenseRef-", "expected a license, found \"LicenseRef-\""),
+            (
+                "DocumentRef-x:MIT",
+                "expected a license, found \"DocumentRef-x:MIT\"",
+            ),
+            ("MIT/X11", "unexpected '/' at position 4"),
+        ];
+        for (expression, message) in cases {
+            let err = LicenseExpression::parse(expression).unwrap_err();
+            assert!(err.to_string().contains(message), "{}: {}", expression, err);
+        }
+    }
+}
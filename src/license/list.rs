@@ -0,0 +1,76 @@
+//! The SPDX License List.
+//!
+//! `licenses.txt` and `exceptions.txt` hold an identifier per line, followed by `deprecated`
+//! for deprecated identifiers. Identifiers are matched case-insensitively, as required by
+//! the SPDX specification.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+/// The version of the embedded SPDX License List.
+pub const LICENSE_LIST_VERSION: &str = "3.27.0";
+
+const LICENSES: &str = include_str!("licenses.txt");
+const EXCEPTIONS: &str = include_str!("exceptions.txt");
+
+/// An identifier of the SPDX License List.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListedId {
+    /// The identifier as spelled in the list.
+    pub id: &'static str,
+    pub deprecated: bool,
+}
+
+/// Looks up a license identifier, such as `MIT` or `GPL-2.0-only`.
+pub fn lookup_license(id: &str) -> Option<ListedId> {
+    static LIST: OnceLock<HashMap<String, ListedId>> = OnceLock::new();
+    LIST.get_or_init(|| parse_list(LICENSES))
+        .get(&id.to_ascii_lowercase())
+        .copied()
+}
+
+/// Looks up a license exception identifier, such as `Classpath-exception-2.0`.
+pub fn lookup_exception(id: &str) -> Option<ListedId> {
+    static LIST: OnceLock<HashMap<String, ListedId>> = OnceLock::new();
+    LIST.get_or_init(|| parse_list(EXCEPTIONS))
+        .get(&id.to_ascii_lowercase())
+        .copied()
+}
+
+// Parses a list, keyed by lowercase identifier.
+fn parse_list(list: &'static str) -> HashMap<String, ListedId> {
+    list.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (id, deprecated) = match line.split_once(' ') {
+                Some((id, flag)) => (id, flag == "deprecated"),
+                None => (line, false),
+            };
+            (id.to_ascii_lowercase(), ListedId { id, deprecated })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_ids() {
+        assert_eq!(
+            lookup_license("apache-2.0"),
+            Some(ListedId {
+                id: "Apache-2.0",
+                deprecated: false
+            })
+        );
+        assert!(lookup_license("GPL-2.0").unwrap().deprecated);
+        assert!(lookup_license("GPL-2.0-or-later").is_some());
+        assert!(lookup_license("Not-A-License").is_none());
+
+        assert_eq!(
+            lookup_exception("Classpath-exception-2.0").unwrap().id,
+            "Classpath-exception-2.0"
+        );
+        assert!(lookup_exception("MIT").is_none());
+    }
+}
@@ -0,0 +1,8 @@
+//! SPDX license expressions.
+//!
+//! License expressions such as `(MIT OR Apache-2.0) AND LicenseRef-1` are parsed into a
+//! `LicenseExpression`, and their identifiers can be checked against a snapshot of the
+//! [SPDX License List](https://spdx.org/licenses/) embedded in the crate.
+
+pub mod expression;
+pub mod list;
@@ -0,0 +1,178 @@
+//! DSSE envelope model and associated structures.
+//!
+//! This module provides the Envelope struct used to transport signed In-Toto statements,
+//! as well as helpers for decoding the wrapped payload into an `InTotoStatementV1`.
+
+use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{helpers::b64_option_serde, intoto::statement::InTotoStatementV1};
+
+/// The DSSE payload type used for In-Toto statements.
+pub const IN_TOTO_PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+
+/// Represents a DSSE envelope.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Envelope {
+    #[serde(rename = "payloadType")]
+    pub payload_type: String,
+    #[serde(with = "b64_option_serde")]
+    #[schemars(with = "String")]
+    pub payload: Vec<u8>,
+    pub signatures: Vec<Signature>,
+}
+
+/// Represents a single signature over a DSSE envelope payload.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Signature {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyid: Option<String>,
+    #[serde(with = "b64_option_serde")]
+    #[schemars(with = "String")]
+    pub sig: Vec<u8>,
+}
+
+impl Envelope {
+    /// Computes the DSSE pre-authentication encoding (PAE) of the envelope payload.
+    ///
+    /// This is the byte string that signatures in the envelope are computed over.
+    pub fn pae(&self) -> Vec<u8> {
+        pae(&self.payload_type, &self.payload)
+    }
+
+    /// Decodes the payload into an In-Toto v1 statement.
+    ///
+    /// Returns an error if the payloadType is not `application/vnd.in-toto+json` or
+    /// if the payload is not a valid In-Toto v1 statement.
+    pub fn statement(&self) -> Result<InTotoStatementV1> {
+        if self.payload_type != IN_TOTO_PAYLOAD_TYPE {
+            return Err(anyhow!(
+                "Unexpected payloadType: {:?}, expected {:?}",
+                self.payload_type,
+                IN_TOTO_PAYLOAD_TYPE
+            ));
+        }
+
+        serde_json::from_slice::<InTotoStatementV1>(&self.payload)
+            .map_err(|e| anyhow!("Failed to deserialize payload: {}", e))
+    }
+}
+
+/// Computes the DSSE v1 pre-authentication encoding for the given payload type and payload.
+///
+/// See: https://github.com/secure-systems-lab/dsse/blob/master/protocol.md#signature-definition
+pub fn pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut encoded = format!(
+        "DSSEv1 {} {} {} ",
+        payload_type.len(),
+        payload_type,
+        payload.len()
+    )
+    .into_bytes();
+    encoded.extend_from_slice(payload);
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose, Engine};
+    use serde_json::json;
+
+    fn get_test_statement_json() -> serde_json::Value {
+        json!({
+            "_type": "https://in-toto.io/Statement/v1",
+            "predicateType": "https://random.type/predicate/v1",
+            "predicate": {},
+            "subject": [
+                {
+                    "name": "example",
+                    "digest": {
+                        "sha256": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"
+                    }
+                }
+            ]
+        })
+    }
+
+    fn get_test_envelope_json(payload_type: &str, payload: &[u8]) -> serde_json::Value {
+        json!({
+            "payloadType": payload_type,
+            "payload": general_purpose::STANDARD.encode(payload),
+            "signatures": [
+                {
+                    "keyid": "test-key",
+                    "sig": "c2lnbmF0dXJl"
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn deserialize_valid_envelope() {
+        let payload = get_test_statement_json().to_string();
+        let json_data = get_test_envelope_json(IN_TOTO_PAYLOAD_TYPE, payload.as_bytes());
+
+        let envelope: Envelope = serde_json::from_value(json_data).unwrap();
+        assert_eq!(envelope.payload_type, IN_TOTO_PAYLOAD_TYPE);
+        assert_eq!(envelope.payload, payload.as_bytes());
+        assert_eq!(envelope.signatures[0].keyid.as_deref(), Some("test-key"));
+        assert_eq!(envelope.signatures[0].sig, b"signature".to_vec());
+
+        let statement = envelope.statement().unwrap();
+        assert_eq!(statement.subject[0].name, "example");
+    }
+
+    #[test]
+    fn serialize_envelope_roundtrip() {
+        let payload = get_test_statement_json().to_string();
+        let json_data = get_test_envelope_json(IN_TOTO_PAYLOAD_TYPE, payload.as_bytes());
+
+        let envelope: Envelope = serde_json::from_value(json_data.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&envelope).unwrap(), json_data);
+    }
+
+    #[test]
+    fn envelope_unexpected_payload_type() {
+        let payload = get_test_statement_json().to_string();
+        let json_data = get_test_envelope_json("application/json", payload.as_bytes());
+
+        let envelope: Envelope = serde_json::from_value(json_data).unwrap();
+        let result = envelope.statement();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unexpected payloadType"));
+    }
+
+    #[test]
+    fn envelope_invalid_payload() {
+        let json_data = get_test_envelope_json(IN_TOTO_PAYLOAD_TYPE, b"{\"invalid\": \"data\"}");
+
+        let envelope: Envelope = serde_json::from_value(json_data).unwrap();
+        assert!(envelope.statement().is_err());
+    }
+
+    #[test]
+    fn pae_encoding() {
+        // Test vector from the DSSE protocol specification.
+        let encoded = pae("http://example.com/HelloWorld", b"hello world");
+        assert_eq!(
+            encoded,
+            b"DSSEv1 29 http://example.com/HelloWorld 11 hello world".to_vec()
+        );
+    }
+
+    #[test]
+    fn deserialize_envelope_invalid_base64() {
+        let json_data = json!({
+            "payloadType": IN_TOTO_PAYLOAD_TYPE,
+            "payload": "not base64!",
+            "signatures": []
+        });
+
+        let result = serde_json::from_value::<Envelope>(json_data);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,5 @@
+//! Dead Simple Signing Envelope (DSSE) models.
+//!
+//! See: https://github.com/secure-systems-lab/dsse/blob/master/envelope.md
+
+pub mod envelope;
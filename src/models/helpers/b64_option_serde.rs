@@ -1,44 +1,82 @@
-//! Custom (de)serialization functions for optional base64-encoded byte arrays.
+//! Custom (de)serialization functions for base64-encoded byte arrays.
 //!
 //! This module provides custom serialization and deserialization functions for
-//! handling `Option<Vec<u8>>` types that are base64-encoded.
+//! handling `Vec<u8>` and `Option<Vec<u8>>` types that are base64-encoded, as well
+//! as the necessary traits to support these functions.
 
 use base64::{engine::general_purpose, Engine};
 use serde::{Deserialize, Deserializer, Serializer};
 
-/// Serializes an optional `Vec<u8>` as a base64-encoded string.
+/// Serializes a `Vec<u8>` or an optional `Vec<u8>` as a base64-encoded string.
 ///
-/// If the input is `Some(Vec<u8>)`, it will be base64-encoded and serialized as a string.
+/// If the input contains bytes, they will be base64-encoded and serialized as a string.
 /// If the input is `None`, it will be serialized as a JSON `null`.
-/// If the input is invalid base64, an error will be returned.
-pub fn serialize<S>(bytes: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+pub fn serialize<S, T>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
+    T: AsBytesRef,
 {
-    match bytes {
+    match bytes.as_bytes_ref() {
         Some(bytes) => serializer.serialize_str(general_purpose::STANDARD.encode(bytes).as_str()),
         None => serializer.serialize_none(),
     }
 }
 
-/// Deserializes a base64-encoded string into an optional `Vec<u8>`.
+/// Deserializes a base64-encoded string into a `Vec<u8>` or an optional `Vec<u8>`.
 ///
-/// If the input is a JSON `null`, it will be deserialized as `None`.
-/// If the input is a base64-encoded string, it will be deserialized into a `Some(Vec<u8>)`.
-pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+/// If the input is a JSON `null`, it will be deserialized as `None` when the target is optional.
+/// If the input is invalid base64, an error will be returned.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
+    T: FromBytes,
 {
     let bytes_option: Option<String> = Option::deserialize(deserializer)?;
 
-    match bytes_option {
-        Some(bytes) => {
-            let deserialized_bytes = general_purpose::STANDARD
+    let bytes = match bytes_option {
+        Some(bytes) => Some(
+            general_purpose::STANDARD
                 .decode(bytes)
-                .map_err(serde::de::Error::custom)?;
-            Ok(Some(deserialized_bytes))
-        }
-        None => Ok(None),
+                .map_err(serde::de::Error::custom)?,
+        ),
+        None => None,
+    };
+    T::from_bytes(bytes).map_err(serde::de::Error::custom)
+}
+
+/// A trait for types that can be created from decoded base64 bytes.
+pub trait FromBytes: Sized {
+    fn from_bytes(bytes: Option<Vec<u8>>) -> Result<Self, &'static str>;
+}
+
+/// A trait for types that can provide a reference to their bytes.
+pub trait AsBytesRef {
+    fn as_bytes_ref(&self) -> Option<&[u8]>;
+}
+
+// Implementations for `Vec<u8>` and `Option<Vec<u8>>` types.
+
+impl FromBytes for Vec<u8> {
+    fn from_bytes(bytes: Option<Vec<u8>>) -> Result<Self, &'static str> {
+        bytes.ok_or("expected a base64-encoded string, found null")
+    }
+}
+
+impl FromBytes for Option<Vec<u8>> {
+    fn from_bytes(bytes: Option<Vec<u8>>) -> Result<Self, &'static str> {
+        Ok(bytes)
+    }
+}
+
+impl AsBytesRef for Vec<u8> {
+    fn as_bytes_ref(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
+
+impl AsBytesRef for Option<Vec<u8>> {
+    fn as_bytes_ref(&self) -> Option<&[u8]> {
+        self.as_deref()
     }
 }
 
@@ -53,6 +91,12 @@ mod tests {
         pub content: Option<Vec<u8>>,
     }
 
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestRequiredStruct {
+        #[serde(with = "super")]
+        pub content: Vec<u8>,
+    }
+
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
     struct TestData {
         pub descriptors: Option<Vec<TestStruct>>,
@@ -126,4 +170,29 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_serde_base64_required() {
+        let value = json!({"content": "aGVsbG8="});
+        let data: TestRequiredStruct =
+            serde_json::from_value(value.clone()).expect("Failed to deserialize bytes");
+        assert_eq!(data.content, b"hello".to_vec());
+
+        let result = serde_json::to_value(&data).expect("Failed to serialize bytes");
+        assert_eq!(result, value);
+    }
+
+    #[test]
+    fn test_deserialize_base64_required_null() {
+        let value = json!({ "content": null });
+        let result = serde_json::from_value::<TestRequiredStruct>(value);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_base64_invalid() {
+        let value = json!({ "content": "not base64!" });
+        let result = serde_json::from_value::<TestStruct>(value);
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,418 @@
+//! Typed parameters of well-known SLSA Provenance v1 build types.
+//!
+//! The `externalParameters` and `internalParameters` of a build definition are defined by
+//! its `buildType`, so the provenance model keeps them as plain JSON. This module provides
+//! typed parameter structs for well-known build types and a registry mapping build type
+//! URIs to them. Build definitions with a registered build type have their parameters
+//! validated when deserialized; parameters of unknown build types are accepted as is.
+
+use anyhow::{anyhow, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+use url::Url;
+
+use crate::models::helpers::url_serde;
+
+/// The build type of GitHub Actions workflows.
+pub const GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE: &str =
+    "https://slsa-framework.github.io/github-actions-buildtypes/workflow/v1";
+
+/// The build type of the generic SLSA GitHub generator's delegated builders.
+pub const SLSA_GITHUB_GENERATOR_GENERIC_BUILD_TYPE: &str =
+    "https://github.com/slsa-framework/slsa-github-generator/delegator-generic@v0";
+
+/// The build type of Google Cloud Build builds run on Google hosted workers.
+pub const GOOGLE_CLOUD_BUILD_BUILD_TYPE: &str =
+    "https://cloud.google.com/build/gcb-buildtypes/google-worker/v1";
+
+/// A reference to a workflow or build configuration file in a source repository.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SourceFile {
+    /// The git ref of the repository, e.g. `refs/heads/main`.
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    #[serde(with = "url_serde")]
+    pub repository: Url,
+    /// The path of the file within the repository.
+    pub path: String,
+}
+
+/// The external parameters of the GitHub Actions workflow build type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GithubActionsWorkflowExternalParameters {
+    pub workflow: SourceFile,
+    /// The inputs of a `workflow_dispatch` event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<Map<String, Value>>,
+    /// The configuration variables referenced by the workflow.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vars: Option<HashMap<String, String>>,
+}
+
+/// The internal parameters of the GitHub Actions workflow build type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GithubActionsWorkflowInternalParameters {
+    pub github: GithubContext,
+}
+
+/// The subset of the GitHub context recorded by the GitHub Actions workflow build type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GithubContext {
+    pub event_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository_owner_id: Option<String>,
+    /// Either `github-hosted` or `self-hosted`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runner_environment: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The external parameters of the generic SLSA GitHub generator build type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SlsaGithubGeneratorGenericExternalParameters {
+    /// The inputs of the delegated builder.
+    pub inputs: Map<String, Value>,
+    /// The configuration variables referenced by the calling workflow.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vars: Option<HashMap<String, String>>,
+    /// The calling workflow.
+    pub workflow: SourceFile,
+}
+
+/// The internal parameters of the generic SLSA GitHub generator build type, the `GITHUB_*`
+/// environment of the calling workflow.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SlsaGithubGeneratorGenericInternalParameters {
+    #[serde(rename = "GITHUB_EVENT_NAME")]
+    pub event_name: String,
+    #[serde(rename = "GITHUB_REF")]
+    pub git_ref: String,
+    #[serde(rename = "GITHUB_REPOSITORY")]
+    pub repository: String,
+    #[serde(rename = "GITHUB_SHA")]
+    pub sha: String,
+    #[serde(rename = "GITHUB_EVENT_PAYLOAD", default)]
+    pub event_payload: Option<Value>,
+    #[serde(flatten)]
+    pub other: HashMap<String, Value>,
+}
+
+/// The external parameters of the Google Cloud Build build type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GoogleCloudBuildExternalParameters {
+    #[serde(rename = "buildConfigSource")]
+    pub build_config_source: SourceFile,
+    /// User defined substitutions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub substitutions: Option<HashMap<String, String>>,
+}
+
+/// The internal parameters of the Google Cloud Build build type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GoogleCloudBuildInternalParameters {
+    /// Substitutions provided by Cloud Build, e.g. `BUILD_ID` and `PROJECT_ID`.
+    #[serde(rename = "systemSubstitutions")]
+    pub system_substitutions: HashMap<String, String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+// A function validating the external and internal parameters of a build type.
+type ValidateFn = dyn Fn(&Value, &Value) -> Result<()> + Send + Sync;
+
+/// A build type registered in the registry.
+#[derive(Clone)]
+pub struct BuildTypeRegistration {
+    /// The build type URI.
+    pub build_type: String,
+    /// A human readable name, e.g. `GithubActionsWorkflow`.
+    pub name: String,
+    validate: Arc<ValidateFn>,
+}
+
+impl BuildTypeRegistration {
+    /// Validates external and internal parameters against this build type.
+    pub fn validate(&self, external_parameters: &Value, internal_parameters: &Value) -> Result<()> {
+        (self.validate)(external_parameters, internal_parameters)
+    }
+}
+
+impl std::fmt::Debug for BuildTypeRegistration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuildTypeRegistration")
+            .field("build_type", &self.build_type)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+// The global build type registry, seeded with the built-in build types. Registrations are
+// shared like those of the predicate registry, as deserializing every build definition
+// looks one up.
+fn registry() -> &'static RwLock<Vec<Arc<BuildTypeRegistration>>> {
+    static REGISTRY: OnceLock<RwLock<Vec<Arc<BuildTypeRegistration>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(builtin_build_types().into_iter().map(Arc::new).collect()))
+}
+
+// The build types with typed parameters in this module.
+fn builtin_build_types() -> Vec<BuildTypeRegistration> {
+    vec![
+        registration::<
+            GithubActionsWorkflowExternalParameters,
+            GithubActionsWorkflowInternalParameters,
+        >(GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE, "GithubActionsWorkflow"),
+        registration::<
+            SlsaGithubGeneratorGenericExternalParameters,
+            SlsaGithubGeneratorGenericInternalParameters,
+        >(
+            SLSA_GITHUB_GENERATOR_GENERIC_BUILD_TYPE,
+            "SlsaGithubGeneratorGeneric",
+        ),
+        registration::<GoogleCloudBuildExternalParameters, GoogleCloudBuildInternalParameters>(
+            GOOGLE_CLOUD_BUILD_BUILD_TYPE,
+            "GoogleCloudBuild",
+        ),
+    ]
+}
+
+fn registration<E, I>(build_type: &str, name: &str) -> BuildTypeRegistration
+where
+    E: DeserializeOwned,
+    I: DeserializeOwned,
+{
+    BuildTypeRegistration {
+        build_type: build_type.to_string(),
+        name: name.to_string(),
+        validate: Arc::new(|external, internal| {
+            E::deserialize(external).map_err(|e| anyhow!("Invalid externalParameters: {}", e))?;
+            I::deserialize(internal).map_err(|e| anyhow!("Invalid internalParameters: {}", e))?;
+            Ok(())
+        }),
+    }
+}
+
+/// Registers a build type whose external and internal parameters deserialize into `E` and
+/// `I` respectively.
+///
+/// Fails if the build type URI or name is already registered.
+pub fn register<E, I>(build_type: &str, name: &str) -> Result<()>
+where
+    E: DeserializeOwned,
+    I: DeserializeOwned,
+{
+    let mut registrations = registry()
+        .write()
+        .map_err(|_| anyhow!("Build type registry lock poisoned"))?;
+    if let Some(existing) = registrations
+        .iter()
+        .find(|r| r.build_type == build_type || r.name == name)
+    {
+        return Err(anyhow!(
+            "Build type {:?} conflicts with registered build type {:?}",
+            build_type,
+            existing.build_type
+        ));
+    }
+
+    registrations.push(Arc::new(registration::<E, I>(build_type, name)));
+    Ok(())
+}
+
+/// Looks up a registered build type by its URI.
+pub fn lookup(build_type: &str) -> Option<Arc<BuildTypeRegistration>> {
+    registry()
+        .read()
+        .ok()?
+        .iter()
+        .find(|r| r.build_type == build_type)
+        .cloned()
+}
+
+/// Returns all registered build types, in registration order.
+pub fn registrations() -> Vec<Arc<BuildTypeRegistration>> {
+    registry().read().map(|r| r.clone()).unwrap_or_default()
+}
+
+/// Validates the parameters of a build type, if it is registered.
+pub fn validate_parameters(
+    build_type: &str,
+    external_parameters: &Value,
+    internal_parameters: &Value,
+) -> Result<()> {
+    match lookup(build_type) {
+        Some(registration) => registration
+            .validate(external_parameters, internal_parameters)
+            .map_err(|e| anyhow!("{} for buildType {:?}", e, build_type)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn github_external() -> Value {
+        json!({
+            "workflow": {
+                "ref": "refs/heads/main",
+                "repository": "https://github.com/octocat/hello-world",
+                "path": ".github/workflow/release.yml"
+            },
+            "inputs": {"build_id": 123456768},
+            "vars": {"MASCOT": "Mona"}
+        })
+    }
+
+    #[test]
+    fn validate_github_actions_workflow() {
+        let internal = json!({"github": {"event_name": "push", "actor_id": "1234567"}});
+        assert!(validate_parameters(
+            GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE,
+            &github_external(),
+            &internal
+        )
+        .is_ok());
+
+        let typed: GithubActionsWorkflowExternalParameters =
+            serde_json::from_value(github_external()).unwrap();
+        assert_eq!(typed.workflow.git_ref, "refs/heads/main");
+
+        let mut missing_path = github_external();
+        missing_path["workflow"]
+            .as_object_mut()
+            .unwrap()
+            .remove("path");
+        let err = validate_parameters(GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE, &missing_path, &internal)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Invalid externalParameters: missing field `path`"));
+        assert!(err.ends_with(&format!(
+            "for buildType {:?}",
+            GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE
+        )));
+
+        let err = validate_parameters(
+            GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE,
+            &github_external(),
+            &json!({"github": {}}),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid internalParameters: missing field `event_name`"));
+    }
+
+    #[test]
+    fn validate_rejects_unknown_external_parameters() {
+        let mut external = github_external();
+        external["unexpected"] = json!(true);
+        assert!(validate_parameters(
+            GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE,
+            &external,
+            &json!({"github": {"event_name": "push"}})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn validate_slsa_github_generator_generic() {
+        let external = json!({
+            "inputs": {"name1": "value1"},
+            "vars": {},
+            "workflow": {
+                "ref": "refs/tags/v1.0.0",
+                "repository": "https://github.com/octocat/hello-world",
+                "path": ".github/workflows/release.yml"
+            }
+        });
+        let internal = json!({
+            "GITHUB_EVENT_NAME": "push",
+            "GITHUB_REF": "refs/tags/v1.0.0",
+            "GITHUB_REPOSITORY": "octocat/hello-world",
+            "GITHUB_SHA": "c27d339ee6075c1f744c5d4b200f7901aad2c369",
+            "GITHUB_RUN_ID": "1536140711"
+        });
+        assert!(validate_parameters(
+            SLSA_GITHUB_GENERATOR_GENERIC_BUILD_TYPE,
+            &external,
+            &internal
+        )
+        .is_ok());
+        assert!(validate_parameters(
+            SLSA_GITHUB_GENERATOR_GENERIC_BUILD_TYPE,
+            &external,
+            &json!({})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn validate_google_cloud_build() {
+        let external = json!({
+            "buildConfigSource": {
+                "ref": "refs/heads/main",
+                "repository": "https://github.com/octocat/hello-world",
+                "path": "cloudbuild.yaml"
+            },
+            "substitutions": {"_ENV": "prod"}
+        });
+        let internal = json!({"systemSubstitutions": {"BUILD_ID": "1234"}});
+        assert!(validate_parameters(GOOGLE_CLOUD_BUILD_BUILD_TYPE, &external, &internal).is_ok());
+
+        let invalid_repository = json!({
+            "buildConfigSource": {
+                "ref": "refs/heads/main",
+                "repository": "not a url",
+                "path": "cloudbuild.yaml"
+            }
+        });
+        assert!(validate_parameters(
+            GOOGLE_CLOUD_BUILD_BUILD_TYPE,
+            &invalid_repository,
+            &internal
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn validate_unknown_build_type() {
+        assert!(validate_parameters(
+            "https://example.com/buildType/v1",
+            &json!("anything"),
+            &json!(null)
+        )
+        .is_ok());
+    }
+
+    #[derive(Deserialize)]
+    struct TestParameters {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    #[test]
+    fn register_build_type() {
+        let build_type = "https://example.com/buildType/registered/v1";
+        register::<TestParameters, Value>(build_type, "TestBuildType").unwrap();
+        assert!(lookup(build_type).is_some());
+        assert!(registrations().iter().any(|r| r.name == "TestBuildType"));
+
+        assert!(validate_parameters(build_type, &json!({"name": "test"}), &json!({})).is_ok());
+        assert!(validate_parameters(build_type, &json!({}), &json!({})).is_err());
+
+        assert!(register::<Value, Value>(build_type, "Other").is_err());
+        assert!(register::<Value, Value>("https://example.com/other", "GoogleCloudBuild").is_err());
+    }
+}
@@ -1,6 +1,12 @@
+pub mod build_type;
 pub mod predicate;
 pub mod provenance;
+pub mod provenance_builder;
+pub mod provenance_v02;
+pub mod registry;
+pub mod resource_uri;
 pub mod statement;
+pub mod vsa;
 
 // NOTE(mlieberman85): Many of the models include additional schemars attributes, e.g. "with".
 // See: https://github.com/GREsau/schemars/issues/89 for more info.
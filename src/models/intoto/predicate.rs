@@ -1,52 +1,109 @@
 //! Custom (de)serialization for various predicate types.
 //!
 //! This module provides an enum `Predicate` and a custom deserialization function
-//! to handle different predicate types, including known types such as `SLSAProvenanceV1`
-//! and generic `Other` variants.
+//! to handle different predicate types, including known types such as `SLSAProvenanceV1`,
+//! predicate types registered at runtime and generic `Other` variants.
 
-use super::provenance::SLSAProvenanceV1Predicate;
+use super::{
+    provenance::SLSAProvenanceV1Predicate, provenance_v02::SLSAProvenanceV02Predicate, registry,
+    vsa::SLSAVerificationSummaryV1Predicate,
+};
 use schemars::JsonSchema;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{Serialize, Serializer};
 use serde_json::Value;
+use std::{any::Any, sync::Arc};
 
 /// An enum representing different predicate types.
 ///
-/// Known predicate types have their own variants, while unknown types are represented
-/// by the `Other` variant, which stores the raw JSON value.
-///
-/// TODO(mlieberman85): Support (de)serializing the predicates based on the
-/// predicateType URL in the statement.
+/// Known predicate types have their own variants, predicate types registered through the
+/// `registry` module are represented by the `Registered` variant, while unknown types are
+/// represented by the `Other` variant, which stores the raw JSON value.
 #[derive(Debug, Serialize, PartialEq, JsonSchema)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum Predicate {
     SLSAProvenanceV1(SLSAProvenanceV1Predicate),
+    SLSAProvenanceV02(SLSAProvenanceV02Predicate),
+    SLSAVerificationSummaryV1(SLSAVerificationSummaryV1Predicate),
+    #[schemars(skip)]
+    Registered(RegisteredPredicate),
     Other(Value),
 }
 
-// Helper function to deserialize a JSON value into the specified type `T`.
-fn deserialize_helper<T: DeserializeOwned>(predicate: &Value) -> Result<T, serde_json::Error> {
-    serde_json::from_value::<T>(predicate.clone())
+/// A predicate of a type registered through the `registry` module.
+///
+/// The raw JSON value is kept for serialization, alongside the typed predicate.
+#[derive(Clone)]
+pub struct RegisteredPredicate {
+    predicate_type: String,
+    value: Value,
+    typed: Arc<dyn Any + Send + Sync>,
+}
+
+impl RegisteredPredicate {
+    /// Creates a registered predicate from its raw JSON value and typed representation.
+    pub fn new(predicate_type: String, value: Value, typed: Arc<dyn Any + Send + Sync>) -> Self {
+        RegisteredPredicate {
+            predicate_type,
+            value,
+            typed,
+        }
+    }
+
+    /// Returns the predicate type URI the predicate was registered under.
+    pub fn predicate_type(&self) -> &str {
+        &self.predicate_type
+    }
+
+    /// Returns the raw JSON value of the predicate.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Returns the typed predicate if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.typed.downcast_ref::<T>()
+    }
+}
+
+impl std::fmt::Debug for RegisteredPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegisteredPredicate")
+            .field("predicate_type", &self.predicate_type)
+            .field("value", &self.value)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RegisteredPredicate {
+    fn eq(&self, other: &Self) -> bool {
+        self.predicate_type == other.predicate_type && self.value == other.value
+    }
+}
+
+impl Serialize for RegisteredPredicate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(serializer)
+    }
 }
 
 /// Deserializes a predicate based on the provided predicate_type.
 ///
-/// If the predicate_type matches a known type, it will deserialize
-/// the predicate to the corresponding struct, otherwise, it will
+/// If the predicate_type is registered in the predicate registry, it will deserialize
+/// the predicate with the registered deserializer, otherwise, it will
 /// deserialize the predicate to the generic `Other` variant.
-/// Update the match for any new predicate types.
 pub fn deserialize_predicate(
     predicate_type: &str,
     predicate_json: &Value,
 ) -> Result<Predicate, serde_json::Error> {
-    match predicate_type {
-        "https://slsa.dev/provenance/v1" => {
-            let slsa_provenance = deserialize_helper::<SLSAProvenanceV1Predicate>(predicate_json)?;
-            Ok(Predicate::SLSAProvenanceV1(slsa_provenance))
-        }
-        _ => {
-            let other_predicate = deserialize_helper::<Value>(predicate_json)?;
-            Ok(Predicate::Other(other_predicate))
+    match registry::lookup(predicate_type) {
+        Some(registration) if registration.predicate_type == predicate_type => {
+            registration.deserialize(predicate_json)
         }
+        _ => Ok(Predicate::Other(predicate_json.clone())),
     }
 }
 
@@ -95,6 +152,37 @@ mod tests {
         assert!(matches!(result, Ok(Predicate::Other(_))));
     }
 
+    #[test]
+    fn test_deserialize_registered_predicate() {
+        #[derive(Debug, serde::Deserialize, JsonSchema)]
+        struct CustomPredicate {
+            key: String,
+        }
+
+        let predicate_type = "https://example.com/predicate-test/v1";
+        registry::register::<CustomPredicate>(
+            predicate_type,
+            "PredicateTestV1",
+            "predicate-test-v1",
+        )
+        .unwrap();
+        let predicate_json = json!({"key": "value"});
+
+        let result = deserialize_predicate(predicate_type, &predicate_json).unwrap();
+        match &result {
+            Predicate::Registered(registered) => {
+                let custom = registered.downcast_ref::<CustomPredicate>().unwrap();
+                assert_eq!(custom.key, "value");
+            }
+            other => panic!("Unexpected predicate: {:?}", other),
+        }
+        assert_eq!(serde_json::to_value(&result).unwrap(), predicate_json);
+
+        // Names and aliases are not predicate type URIs.
+        let result = deserialize_predicate("predicate-test-v1", &predicate_json);
+        assert!(matches!(result, Ok(Predicate::Other(_))));
+    }
+
     #[test]
     fn test_deserialize_invalid_predicate() {
         let predicate_type = "https://slsa.dev/provenance/v1";
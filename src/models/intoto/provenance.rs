@@ -3,15 +3,20 @@
 //! This module provides structs for the SLSAProvenanceV1Predicate and its related structures.
 //! It also includes the necessary (de)serialization code for handling SLSA provenance predicates.
 
-use crate::models::helpers::{b64_option_serde, url_serde};
+use crate::models::{
+    helpers::{b64_option_serde, url_serde},
+    intoto::{build_type, resource_uri::ResourceUri, statement::DigestSet},
+};
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use url::Url;
 
+/// The predicate type of the SLSA Provenance v1 Predicate.
+pub const SLSA_PROVENANCE_V1_PREDICATE_TYPE: &str = "https://slsa.dev/provenance/v1";
+
 /// A structure representing the SLSA Provenance v1 Predicate.
-#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct SLSAProvenanceV1Predicate {
     #[serde(rename = "buildDefinition")]
     pub build_definition: BuildDefinition,
@@ -20,7 +25,9 @@ pub struct SLSAProvenanceV1Predicate {
 }
 
 /// A structure representing the build definition of the SLSA Provenance v1 Predicate.
-#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+// Parameters of well-known build types are validated when deserialized, see `build_type`.
+#[serde(try_from = "UncheckedBuildDefinition")]
 pub struct BuildDefinition {
     #[serde(rename = "buildType", with = "url_serde")]
     #[schemars(with = "Url")]
@@ -33,8 +40,60 @@ pub struct BuildDefinition {
     pub resolved_dependencies: Vec<ResourceDescriptor>,
 }
 
+impl BuildDefinition {
+    /// Deserializes the external parameters into a typed struct, e.g. one of the parameter
+    /// structs in `crate::models::intoto::build_type`.
+    pub fn external_parameters_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(&self.external_parameters)
+    }
+
+    /// Deserializes the internal parameters into a typed struct.
+    pub fn internal_parameters_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(&self.internal_parameters)
+    }
+
+    /// Validates the parameters against the build type, if it is a registered build type.
+    pub fn validate_parameters(&self) -> anyhow::Result<()> {
+        build_type::validate_parameters(
+            self.build_type.as_str(),
+            &self.external_parameters,
+            &self.internal_parameters,
+        )
+    }
+}
+
+// Helper struct to deserialize a build definition before validating its parameters.
+#[derive(Deserialize)]
+struct UncheckedBuildDefinition {
+    #[serde(rename = "buildType", with = "url_serde")]
+    build_type: Url,
+    #[serde(rename = "externalParameters")]
+    external_parameters: serde_json::Value,
+    #[serde(rename = "internalParameters")]
+    internal_parameters: serde_json::Value,
+    #[serde(rename = "resolvedDependencies")]
+    resolved_dependencies: Vec<ResourceDescriptor>,
+}
+
+impl TryFrom<UncheckedBuildDefinition> for BuildDefinition {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedBuildDefinition) -> Result<Self, Self::Error> {
+        let build_definition = BuildDefinition {
+            build_type: unchecked.build_type,
+            external_parameters: unchecked.external_parameters,
+            internal_parameters: unchecked.internal_parameters,
+            resolved_dependencies: unchecked.resolved_dependencies,
+        };
+        build_definition
+            .validate_parameters()
+            .map_err(|e| e.to_string())?;
+        Ok(build_definition)
+    }
+}
+
 /// A structure representing the run details of the SLSA Provenance v1 Predicate.
-#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct RunDetails {
     pub builder: Builder,
     pub metadata: Metadata,
@@ -43,7 +102,7 @@ pub struct RunDetails {
 }
 
 /// A structure representing the builder information of the SLSA Provenance v1 Predicate.
-#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Builder {
     #[serde(with = "url_serde")]
     #[schemars(with = "Url")]
@@ -58,7 +117,7 @@ pub struct Builder {
 }
 
 /// A structure representing the metadata of the SLSA Provenance v1 Predicate.
-#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Metadata {
     #[serde(rename = "invocationId")]
     pub invocation_id: String,
@@ -69,12 +128,14 @@ pub struct Metadata {
 }
 
 /// A structure representing a resource descriptor in the SLSA Provenance v1 Predicate.
-#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+///
+/// At least one of `uri`, `digest` or `content` must be set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(try_from = "UncheckedResourceDescriptor")]
 pub struct ResourceDescriptor {
-    #[serde(with = "url_serde")]
-    #[schemars(with = "Url")]
-    pub uri: Url,
-    pub digest: Option<HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<ResourceUri>,
+    pub digest: Option<DigestSet>,
     pub name: Option<String>,
     #[serde(
         rename = "downloadLocation",
@@ -100,12 +161,84 @@ pub struct ResourceDescriptor {
     pub annotations: Option<serde_json::Value>,
 }
 
+impl ResourceDescriptor {
+    /// Creates a resource descriptor with only a URI and digest.
+    pub fn new(uri: Option<ResourceUri>, digest: Option<DigestSet>) -> Self {
+        ResourceDescriptor {
+            uri,
+            digest,
+            name: None,
+            download_location: None,
+            media_type: None,
+            content: None,
+            annotations: None,
+        }
+    }
+
+    /// Returns an error unless at least one of `uri`, `digest` or `content` is set.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.uri.is_none() && self.digest.is_none() && self.content.is_none() {
+            return Err(
+                "resource descriptor must have at least one of uri, digest or content".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+// Helper struct to deserialize a resource descriptor before checking its required fields.
+#[derive(Deserialize)]
+struct UncheckedResourceDescriptor {
+    #[serde(default)]
+    uri: Option<ResourceUri>,
+    #[serde(default)]
+    digest: Option<DigestSet>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(rename = "downloadLocation", with = "url_serde", default)]
+    download_location: Option<Url>,
+    #[serde(rename = "mediaType", default)]
+    media_type: Option<String>,
+    #[serde(with = "b64_option_serde", default)]
+    content: Option<Vec<u8>>,
+    #[serde(default)]
+    annotations: Option<serde_json::Value>,
+}
+
+impl TryFrom<UncheckedResourceDescriptor> for ResourceDescriptor {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedResourceDescriptor) -> Result<Self, Self::Error> {
+        let descriptor = ResourceDescriptor {
+            uri: unchecked.uri,
+            digest: unchecked.digest,
+            name: unchecked.name,
+            download_location: unchecked.download_location,
+            media_type: unchecked.media_type,
+            content: unchecked.content,
+            annotations: unchecked.annotations,
+        };
+        descriptor.validate()?;
+        Ok(descriptor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::intoto::statement::Algorithm;
     use maplit::hashmap;
     use serde_json::json;
 
+    fn get_test_digest() -> Option<DigestSet> {
+        Some(
+            DigestSet::new(
+                hashmap! {Algorithm::Custom("algorithm1".to_string()) => "digest1".to_string()},
+            )
+            .unwrap(),
+        )
+    }
+
     fn get_test_slsa_provenance() -> SLSAProvenanceV1Predicate {
         SLSAProvenanceV1Predicate {
             build_definition: BuildDefinition {
@@ -113,8 +246,8 @@ mod tests {
                 external_parameters: json!({"key": "value"}),
                 internal_parameters: json!({"key": "value"}),
                 resolved_dependencies: vec![ResourceDescriptor {
-                    uri: Url::parse("https://example.com/dependency1").unwrap(),
-                    digest: Some(hashmap! {"algorithm1".to_string() => "digest1".to_string()}),
+                    uri: Some("https://example.com/dependency1".parse().unwrap()),
+                    digest: get_test_digest(),
                     name: Some("dependency1".to_string()),
                     download_location: Some(Url::parse("https://example.com/download1").unwrap()),
                     media_type: Some("media/type1".to_string()),
@@ -126,8 +259,8 @@ mod tests {
                 builder: Builder {
                     id: Url::parse("https://example.com/builder/v1").unwrap(),
                     builder_dependencies: Some(vec![ResourceDescriptor {
-                        uri: Url::parse("https://example.com/builder/dependency1").unwrap(),
-                        digest: Some(hashmap! {"algorithm1".to_string() => "digest1".to_string()}),
+                        uri: Some("https://example.com/builder/dependency1".parse().unwrap()),
+                        digest: get_test_digest(),
                         name: Some("builder_dependency1".to_string()),
                         download_location: Some(
                             Url::parse("https://example.com/builder/download1").unwrap(),
@@ -150,8 +283,8 @@ mod tests {
                     ),
                 },
                 byproducts: Some(vec![ResourceDescriptor {
-                    uri: Url::parse("https://example.com/byproduct1").unwrap(),
-                    digest: Some(hashmap! {"algorithm1".to_string() => "digest1".to_string()}),
+                    uri: Some("https://example.com/byproduct1".parse().unwrap()),
+                    digest: get_test_digest(),
                     name: Some("byproduct1".to_string()),
                     download_location: Some(
                         Url::parse("https://example.com/byproduct/download1").unwrap(),
@@ -252,4 +385,100 @@ mod tests {
 
         assert_eq!(serialized_provenance, expected_json_data);
     }
+
+    #[test]
+    fn deserialize_resource_descriptor_validates_digest() {
+        let valid = json!({
+            "uri": "git+https://github.com/octocat/hello-world@refs/heads/main",
+            "digest": {
+                "gitCommit": "c27d339ee6075c1f744c5d4b200f7901aad2c369"
+            }
+        });
+        assert!(serde_json::from_value::<ResourceDescriptor>(valid).is_ok());
+
+        let invalid = json!({
+            "uri": "git+https://github.com/octocat/hello-world@refs/heads/main",
+            "digest": {
+                "gitCommit": "c27d339"
+            }
+        });
+        assert!(serde_json::from_value::<ResourceDescriptor>(invalid).is_err());
+    }
+
+    #[test]
+    fn deserialize_resource_descriptor_requires_uri_digest_or_content() {
+        let digest_only = json!({
+            "name": "hello.txt",
+            "digest": {"sha256": "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03"}
+        });
+        let descriptor = serde_json::from_value::<ResourceDescriptor>(digest_only.clone()).unwrap();
+        assert_eq!(descriptor.uri, None);
+        assert_eq!(
+            serde_json::to_value(&descriptor).unwrap()["uri"],
+            json!(null)
+        );
+
+        let name_only = json!({"name": "hello.txt"});
+        let err = serde_json::from_value::<ResourceDescriptor>(name_only)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "resource descriptor must have at least one of uri, digest or content"
+        );
+    }
+
+    #[test]
+    fn deserialize_resource_descriptor_uris() {
+        for (uri, expected) in [
+            ("pkg:npm/hello-world@1.0.0", "Purl"),
+            (
+                "git+https://github.com/octocat/hello-world@refs/heads/main",
+                "Vcs",
+            ),
+            ("https://example.com/dependency1", "Url"),
+            ("dist/hello.tar.gz", "Relative"),
+        ] {
+            let descriptor =
+                serde_json::from_value::<ResourceDescriptor>(json!({"uri": uri})).unwrap();
+            let kind = match descriptor.uri.as_ref().unwrap() {
+                ResourceUri::Purl(_) => "Purl",
+                ResourceUri::Vcs(_) => "Vcs",
+                ResourceUri::Url(_) => "Url",
+                ResourceUri::Relative(_) => "Relative",
+            };
+            assert_eq!(kind, expected);
+            assert_eq!(
+                serde_json::to_value(&descriptor).unwrap()["uri"],
+                json!(uri)
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_build_definition_validates_known_build_type() {
+        let mut json_data = get_test_slsa_provenance_json();
+        json_data["buildDefinition"]["buildType"] =
+            json!(build_type::GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE);
+        let err = serde_json::from_value::<SLSAProvenanceV1Predicate>(json_data.clone())
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Invalid externalParameters: unknown field `key`"));
+
+        json_data["buildDefinition"]["externalParameters"] = json!({
+            "workflow": {
+                "ref": "refs/heads/main",
+                "repository": "https://github.com/octocat/hello-world",
+                "path": ".github/workflow/release.yml"
+            }
+        });
+        json_data["buildDefinition"]["internalParameters"] =
+            json!({"github": {"event_name": "push"}});
+        let provenance: SLSAProvenanceV1Predicate = serde_json::from_value(json_data).unwrap();
+        let external = provenance
+            .build_definition
+            .external_parameters_as::<build_type::GithubActionsWorkflowExternalParameters>()
+            .unwrap();
+        assert_eq!(external.workflow.path, ".github/workflow/release.yml");
+    }
 }
@@ -0,0 +1,511 @@
+//! Fluent builders for SLSA provenance v1 predicates and statements.
+//!
+//! This module provides the ProvenanceBuilder and ResourceDescriptorBuilder, which avoid
+//! hand-assembling the nested provenance structs. Required fields are checked, and URIs
+//! and digests validated, when `build()` is called.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use url::Url;
+
+use crate::models::intoto::{
+    predicate::Predicate,
+    provenance::{
+        BuildDefinition, Builder, Metadata, ResourceDescriptor, RunDetails,
+        SLSAProvenanceV1Predicate, SLSA_PROVENANCE_V1_PREDICATE_TYPE,
+    },
+    resource_uri::ResourceUri,
+    statement::{Algorithm, DigestSet, InTotoStatementV1, Subject, STATEMENT_TYPE_V1},
+};
+
+/// A builder for SLSAProvenanceV1Predicate and statements wrapping it.
+///
+/// ```
+/// use spector::models::intoto::provenance_builder::ProvenanceBuilder;
+///
+/// let provenance = ProvenanceBuilder::new("https://example.com/buildType/v1")
+///     .external_param("repository", "https://github.com/octocat/hello-world")
+///     .builder_id("https://example.com/builder")
+///     .invocation_id("1")
+///     .started_now()
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProvenanceBuilder {
+    build_type: String,
+    external_parameters: Map<String, Value>,
+    internal_parameters: Map<String, Value>,
+    resolved_dependencies: Vec<ResourceDescriptor>,
+    builder_id: Option<String>,
+    builder_version: Option<String>,
+    builder_dependencies: Vec<ResourceDescriptor>,
+    invocation_id: Option<String>,
+    started_on: Option<DateTime<Utc>>,
+    finished_on: Option<DateTime<Utc>>,
+    byproducts: Vec<ResourceDescriptor>,
+    subjects: Vec<Subject>,
+}
+
+impl ProvenanceBuilder {
+    /// Creates a builder for provenance with the given build type URI.
+    pub fn new(build_type: impl Into<String>) -> Self {
+        ProvenanceBuilder {
+            build_type: build_type.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets an external parameter, replacing any previous value for the key.
+    pub fn external_param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.external_parameters.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets an internal parameter, replacing any previous value for the key.
+    pub fn internal_param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.internal_parameters.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds a resolved dependency.
+    pub fn resolved_dependency(mut self, dependency: ResourceDescriptor) -> Self {
+        self.resolved_dependencies.push(dependency);
+        self
+    }
+
+    /// Sets the builder ID URI. Required.
+    pub fn builder_id(mut self, id: impl Into<String>) -> Self {
+        self.builder_id = Some(id.into());
+        self
+    }
+
+    /// Sets the builder version.
+    pub fn builder_version(mut self, version: impl Into<String>) -> Self {
+        self.builder_version = Some(version.into());
+        self
+    }
+
+    /// Adds a builder dependency.
+    pub fn builder_dependency(mut self, dependency: ResourceDescriptor) -> Self {
+        self.builder_dependencies.push(dependency);
+        self
+    }
+
+    /// Sets the invocation ID. Required.
+    pub fn invocation_id(mut self, id: impl Into<String>) -> Self {
+        self.invocation_id = Some(id.into());
+        self
+    }
+
+    /// Sets the time the build started. Required.
+    pub fn started_on(mut self, started_on: DateTime<Utc>) -> Self {
+        self.started_on = Some(started_on);
+        self
+    }
+
+    /// Sets the time the build started to now.
+    pub fn started_now(self) -> Self {
+        self.started_on(Utc::now())
+    }
+
+    /// Sets the time the build finished.
+    pub fn finished_on(mut self, finished_on: DateTime<Utc>) -> Self {
+        self.finished_on = Some(finished_on);
+        self
+    }
+
+    /// Sets the time the build finished to now.
+    pub fn finished_now(self) -> Self {
+        self.finished_on(Utc::now())
+    }
+
+    /// Adds a byproduct.
+    pub fn byproduct(mut self, byproduct: ResourceDescriptor) -> Self {
+        self.byproducts.push(byproduct);
+        self
+    }
+
+    /// Adds a subject, only used by `build_statement`.
+    pub fn subject(mut self, name: impl Into<String>, digest: DigestSet) -> Self {
+        self.subjects.push(Subject {
+            name: name.into(),
+            digest,
+        });
+        self
+    }
+
+    /// Builds the predicate.
+    ///
+    /// Fails if the builder ID, invocation ID or start time are missing, a URI is invalid,
+    /// the build finished before it started, or the parameters are invalid for a well-known
+    /// build type.
+    pub fn build(self) -> Result<SLSAProvenanceV1Predicate> {
+        let missing = [
+            ("runDetails.builder.id", self.builder_id.is_none()),
+            (
+                "runDetails.metadata.invocationId",
+                self.invocation_id.is_none(),
+            ),
+            ("runDetails.metadata.startedOn", self.started_on.is_none()),
+        ]
+        .iter()
+        .filter(|(_, missing)| *missing)
+        .map(|(field, _)| *field)
+        .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(anyhow!("Missing required field(s): {}", missing.join(", ")));
+        }
+
+        let started_on = self.started_on.unwrap_or_default();
+        if let Some(finished_on) = self.finished_on {
+            if finished_on < started_on {
+                return Err(anyhow!(
+                    "finishedOn {} is before startedOn {}",
+                    finished_on,
+                    started_on
+                ));
+            }
+        }
+
+        let build_definition = BuildDefinition {
+            build_type: parse_url("buildDefinition.buildType", &self.build_type)?,
+            external_parameters: Value::Object(self.external_parameters),
+            internal_parameters: Value::Object(self.internal_parameters),
+            resolved_dependencies: self.resolved_dependencies,
+        };
+        build_definition.validate_parameters()?;
+
+        Ok(SLSAProvenanceV1Predicate {
+            build_definition,
+            run_details: RunDetails {
+                builder: Builder {
+                    id: parse_url(
+                        "runDetails.builder.id",
+                        self.builder_id.as_deref().unwrap_or_default(),
+                    )?,
+                    builder_dependencies: non_empty(self.builder_dependencies),
+                    version: self.builder_version,
+                },
+                metadata: Metadata {
+                    invocation_id: self.invocation_id.unwrap_or_default(),
+                    started_on,
+                    finished_on: self.finished_on,
+                },
+                byproducts: non_empty(self.byproducts),
+            },
+        })
+    }
+
+    /// Builds an In-Toto v1 statement with the predicate and the added subjects.
+    ///
+    /// Fails if no subjects were added, or the predicate can't be built.
+    pub fn build_statement(mut self) -> Result<InTotoStatementV1> {
+        let subject = std::mem::take(&mut self.subjects);
+        if subject.is_empty() {
+            return Err(anyhow!("Missing required field(s): subject"));
+        }
+
+        Ok(InTotoStatementV1 {
+            _type: Url::parse(STATEMENT_TYPE_V1)?,
+            subject,
+            predicate_type: Url::parse(SLSA_PROVENANCE_V1_PREDICATE_TYPE)?,
+            predicate: Predicate::SLSAProvenanceV1(self.build()?),
+        })
+    }
+}
+
+/// A builder for ResourceDescriptor.
+///
+/// Use `ResourceDescriptorBuilder::default()` for a descriptor without a URI, which must
+/// then have a digest or content.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceDescriptorBuilder {
+    uri: Option<String>,
+    digest: HashMap<Algorithm, String>,
+    name: Option<String>,
+    download_location: Option<String>,
+    media_type: Option<String>,
+    content: Option<Vec<u8>>,
+    annotations: Map<String, Value>,
+}
+
+impl ResourceDescriptorBuilder {
+    /// Creates a builder for a resource descriptor with the given URI.
+    pub fn new(uri: impl Into<String>) -> Self {
+        ResourceDescriptorBuilder::default().uri(uri)
+    }
+
+    /// Sets the URI, e.g. a URL, package URL, VCS URI or relative reference.
+    pub fn uri(mut self, uri: impl Into<String>) -> Self {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    /// Adds a digest, validated when the descriptor is built.
+    pub fn digest(mut self, algorithm: Algorithm, digest: impl Into<String>) -> Self {
+        self.digest.insert(algorithm, digest.into());
+        self
+    }
+
+    /// Sets the name.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the download location URI.
+    pub fn download_location(mut self, download_location: impl Into<String>) -> Self {
+        self.download_location = Some(download_location.into());
+        self
+    }
+
+    /// Sets the media type.
+    pub fn media_type(mut self, media_type: impl Into<String>) -> Self {
+        self.media_type = Some(media_type.into());
+        self
+    }
+
+    /// Sets the content.
+    pub fn content(mut self, content: impl Into<Vec<u8>>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Sets an annotation, replacing any previous value for the key.
+    pub fn annotation(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.annotations.insert(key.into(), value.into());
+        self
+    }
+
+    /// Builds the resource descriptor.
+    ///
+    /// Fails if none of the URI, digest or content are set, or a URI or digest is invalid.
+    pub fn build(self) -> Result<ResourceDescriptor> {
+        if self.uri.is_none() && self.digest.is_empty() && self.content.is_none() {
+            return Err(anyhow!(
+                "Missing required field(s): one of uri, digest or content"
+            ));
+        }
+
+        Ok(ResourceDescriptor {
+            uri: self
+                .uri
+                .map(|uri| {
+                    uri.parse::<ResourceUri>()
+                        .map_err(|e| anyhow!("Invalid uri: {}", e))
+                })
+                .transpose()?,
+            digest: if self.digest.is_empty() {
+                None
+            } else {
+                Some(DigestSet::new(self.digest)?)
+            },
+            name: self.name,
+            download_location: self
+                .download_location
+                .map(|location| parse_url("downloadLocation", &location))
+                .transpose()?,
+            media_type: self.media_type,
+            content: self.content,
+            annotations: if self.annotations.is_empty() {
+                None
+            } else {
+                Some(Value::Object(self.annotations))
+            },
+        })
+    }
+}
+
+// Parses a URL, naming the field in the error.
+fn parse_url(field: &str, url: &str) -> Result<Url> {
+    Url::parse(url).map_err(|e| anyhow!("Invalid {} {:?}: {}", field, url, e))
+}
+
+// Returns None for an empty list, matching how optional lists are omitted when deserialized.
+fn non_empty<T>(list: Vec<T>) -> Option<Vec<T>> {
+    if list.is_empty() {
+        None
+    } else {
+        Some(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::intoto::build_type::GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE;
+    use maplit::hashmap;
+    use serde_json::json;
+
+    const SHA256: &str = "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4";
+
+    fn started_on() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2023-01-01T12:34:56Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn build_provenance_matches_fixture() {
+        let fixture = serde_json::from_str::<InTotoStatementV1>(include_str!(
+            "../../../tests/fixtures/slsa_provenance_v1.json"
+        ))
+        .unwrap();
+
+        let statement =
+            ProvenanceBuilder::new("https://slsa-framework.github.io/github-actions-buildtypes/workflow/v1")
+                .external_param(
+                    "inputs",
+                    json!({
+                        "build_id": 123456768,
+                        "deploy_target": "deployment_sys_1a",
+                        "perform_deploy": "true"
+                    }),
+                )
+                .external_param("vars", json!({"MASCOT": "Mona"}))
+                .external_param(
+                    "workflow",
+                    json!({
+                        "ref": "refs/heads/main",
+                        "repository": "https://github.com/octocat/hello-world",
+                        "path": ".github/workflow/release.yml"
+                    }),
+                )
+                .internal_param(
+                    "github",
+                    json!({"actor_id": "1234567", "event_name": "workflow_dispatch"}),
+                )
+                .resolved_dependency(
+                    ResourceDescriptorBuilder::new(
+                        "git+https://github.com/octocat/hello-world@refs/heads/main",
+                    )
+                    .digest(
+                        Algorithm::GitCommit,
+                        "c27d339ee6075c1f744c5d4b200f7901aad2c369",
+                    )
+                    .build()
+                    .unwrap(),
+                )
+                .resolved_dependency(
+                    ResourceDescriptorBuilder::new(
+                        "https://github.com/actions/virtual-environments/releases/tag/ubuntu20/20220515.1",
+                    )
+                    .build()
+                    .unwrap(),
+                )
+                .builder_id("https://github.com/slsa-framework/slsa-github-generator/.github/workflows/builder_go_slsa3.yml@refs/tags/v0.0.1")
+                .invocation_id(
+                    "https://github.com/octocat/hello-world/actions/runs/1536140711/attempts/1",
+                )
+                .started_on(started_on())
+                .subject(
+                    "_",
+                    DigestSet::new(hashmap! {Algorithm::Sha256 => SHA256.to_string()}).unwrap(),
+                )
+                .build_statement()
+                .unwrap();
+
+        assert_eq!(statement.subject, fixture.subject);
+        assert_eq!(statement.predicate_type, fixture.predicate_type);
+        assert_eq!(statement.predicate, fixture.predicate);
+    }
+
+    #[test]
+    fn build_missing_required_fields() {
+        let err = ProvenanceBuilder::new("https://example.com/buildType/v1")
+            .invocation_id("1")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Missing required field(s): runDetails.builder.id, runDetails.metadata.startedOn"
+        );
+    }
+
+    #[test]
+    fn build_invalid_fields() {
+        let builder = ProvenanceBuilder::new("not a uri")
+            .builder_id("https://example.com/builder")
+            .invocation_id("1")
+            .started_on(started_on());
+        let err = builder.clone().build().unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid buildDefinition.buildType"));
+
+        let err = ProvenanceBuilder::new("https://example.com/buildType/v1")
+            .builder_id("https://example.com/builder")
+            .invocation_id("1")
+            .started_on(started_on())
+            .finished_on(started_on() - chrono::Duration::seconds(1))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("is before startedOn"));
+
+        let err = ProvenanceBuilder::new(GITHUB_ACTIONS_WORKFLOW_BUILD_TYPE)
+            .external_param("workflow", json!({"ref": "refs/heads/main"}))
+            .internal_param("github", json!({"event_name": "push"}))
+            .builder_id("https://example.com/builder")
+            .invocation_id("1")
+            .started_on(started_on())
+            .build()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid externalParameters: missing field `repository`"));
+    }
+
+    #[test]
+    fn build_statement_requires_subject() {
+        let err = ProvenanceBuilder::new("https://example.com/buildType/v1")
+            .builder_id("https://example.com/builder")
+            .invocation_id("1")
+            .started_now()
+            .build_statement()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Missing required field(s): subject");
+    }
+
+    #[test]
+    fn build_resource_descriptor() {
+        let descriptor = ResourceDescriptorBuilder::new("https://example.com/dependency1")
+            .digest(Algorithm::Sha256, SHA256)
+            .name("dependency1")
+            .download_location("https://example.com/download1")
+            .media_type("media/type1")
+            .content(b"content1".to_vec())
+            .annotation("key", "value")
+            .build()
+            .unwrap();
+        assert_eq!(descriptor.name.as_deref(), Some("dependency1"));
+        assert_eq!(descriptor.annotations, Some(json!({"key": "value"})));
+
+        let invalid = ResourceDescriptorBuilder::new("https://example.com/dependency1")
+            .digest(Algorithm::Sha256, "abc")
+            .build();
+        assert!(invalid.is_err());
+
+        let descriptor = ResourceDescriptorBuilder::new("pkg:npm/hello-world@1.0.0")
+            .build()
+            .unwrap();
+        assert!(matches!(descriptor.uri, Some(ResourceUri::Purl(_))));
+
+        let descriptor = ResourceDescriptorBuilder::default()
+            .digest(Algorithm::Sha256, SHA256)
+            .build()
+            .unwrap();
+        assert_eq!(descriptor.uri, None);
+
+        let err = ResourceDescriptorBuilder::default()
+            .name("dependency1")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Missing required field(s): one of uri, digest or content"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! SLSA provenance v0.2 predicate model and associated structures.
+//!
+//! This module provides structs for the SLSAProvenanceV02Predicate, still emitted by many
+//! older builders. See `crate::convert::slsa` for converting it to the v1 predicate.
+
+use crate::models::{helpers::url_serde, intoto::statement::DigestSet};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The predicate type of the SLSA Provenance v0.2 Predicate.
+pub const SLSA_PROVENANCE_V02_PREDICATE_TYPE: &str = "https://slsa.dev/provenance/v0.2";
+
+/// A structure representing the SLSA Provenance v0.2 Predicate.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct SLSAProvenanceV02Predicate {
+    pub builder: Builder,
+    #[serde(rename = "buildType", with = "url_serde")]
+    #[schemars(with = "Url")]
+    pub build_type: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation: Option<Invocation>,
+    #[serde(
+        rename = "buildConfig",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_config: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub materials: Option<Vec<Material>>,
+}
+
+/// A structure representing the builder of the SLSA Provenance v0.2 Predicate.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[schemars(rename = "SLSAProvenanceV02Builder")]
+pub struct Builder {
+    #[serde(with = "url_serde")]
+    #[schemars(with = "Url")]
+    pub id: Url,
+}
+
+/// A structure representing the invocation of the SLSA Provenance v0.2 Predicate.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Invocation {
+    #[serde(
+        rename = "configSource",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub config_source: Option<ConfigSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<serde_json::Value>,
+}
+
+/// A structure representing the config source of the SLSA Provenance v0.2 Predicate.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ConfigSource {
+    #[serde(with = "url_serde", default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<Url>")]
+    pub uri: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<DigestSet>,
+    #[serde(
+        rename = "entryPoint",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub entry_point: Option<String>,
+}
+
+/// A structure representing the metadata of the SLSA Provenance v0.2 Predicate.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[schemars(rename = "SLSAProvenanceV02Metadata")]
+pub struct Metadata {
+    // Also read from `buildInvocationID`, the spelling emitted by in-toto-golang and
+    // slsa-github-generator.
+    #[serde(
+        rename = "buildInvocationId",
+        alias = "buildInvocationID",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_invocation_id: Option<String>,
+    #[serde(
+        rename = "buildStartedOn",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_started_on: Option<DateTime<Utc>>,
+    #[serde(
+        rename = "buildFinishedOn",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_finished_on: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completeness: Option<Completeness>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reproducible: Option<bool>,
+}
+
+/// A structure representing the completeness claims of the SLSA Provenance v0.2 Predicate.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Completeness {
+    #[serde(default)]
+    pub parameters: bool,
+    #[serde(default)]
+    pub environment: bool,
+    #[serde(default)]
+    pub materials: bool,
+}
+
+/// A structure representing a material of the SLSA Provenance v0.2 Predicate.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Material {
+    #[serde(with = "url_serde", default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<Url>")]
+    pub uri: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<DigestSet>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn get_test_slsa_provenance_v02_json() -> serde_json::Value {
+        json!({
+            "builder": {
+                "id": "https://github.com/slsa-framework/slsa-github-generator/.github/workflows/generator_generic_slsa3.yml@refs/tags/v1.5.0"
+            },
+            "buildType": "https://github.com/slsa-framework/slsa-github-generator/generic@v1",
+            "invocation": {
+                "configSource": {
+                    "uri": "git+https://github.com/octocat/hello-world@refs/heads/main",
+                    "digest": {
+                        "sha1": "c27d339ee6075c1f744c5d4b200f7901aad2c369"
+                    },
+                    "entryPoint": ".github/workflows/release.yml"
+                },
+                "parameters": {},
+                "environment": {
+                    "github_event_name": "push"
+                }
+            },
+            "metadata": {
+                "buildInvocationId": "1536140711-1",
+                "buildStartedOn": "2023-01-01T12:34:56Z",
+                "completeness": {
+                    "parameters": true,
+                    "environment": false,
+                    "materials": false
+                },
+                "reproducible": false
+            },
+            "materials": [
+                {
+                    "uri": "git+https://github.com/octocat/hello-world@refs/heads/main",
+                    "digest": {
+                        "sha1": "c27d339ee6075c1f744c5d4b200f7901aad2c369"
+                    }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn deserialize_slsa_provenance_v02() {
+        let provenance: SLSAProvenanceV02Predicate =
+            serde_json::from_value(get_test_slsa_provenance_v02_json()).unwrap();
+
+        let invocation = provenance.invocation.as_ref().unwrap();
+        assert_eq!(
+            invocation
+                .config_source
+                .as_ref()
+                .unwrap()
+                .entry_point
+                .as_deref(),
+            Some(".github/workflows/release.yml")
+        );
+        let metadata = provenance.metadata.as_ref().unwrap();
+        assert!(metadata.completeness.as_ref().unwrap().parameters);
+        assert_eq!(metadata.reproducible, Some(false));
+        assert_eq!(metadata.build_finished_on, None);
+        assert_eq!(provenance.materials.as_ref().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn serialize_slsa_provenance_v02_roundtrip() {
+        let json_data = get_test_slsa_provenance_v02_json();
+        let provenance: SLSAProvenanceV02Predicate =
+            serde_json::from_value(json_data.clone()).unwrap();
+
+        assert_eq!(serde_json::to_value(provenance).unwrap(), json_data);
+    }
+
+    #[test]
+    fn deserialize_slsa_provenance_v02_minimal() {
+        let json_data = json!({
+            "builder": {"id": "https://example.com/builder"},
+            "buildType": "https://example.com/buildType/v1"
+        });
+        let provenance: SLSAProvenanceV02Predicate = serde_json::from_value(json_data).unwrap();
+        assert!(provenance.invocation.is_none());
+        assert!(provenance.materials.is_none());
+
+        let missing_builder = json!({"buildType": "https://example.com/buildType/v1"});
+        assert!(serde_json::from_value::<SLSAProvenanceV02Predicate>(missing_builder).is_err());
+    }
+}
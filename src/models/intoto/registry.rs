@@ -0,0 +1,294 @@
+//! A registry of predicate types.
+//!
+//! The registry maps predicate type URIs to a deserializer and JSON schema, so that crates
+//! using Spector as a library can plug in their own predicate types without forking the
+//! `Predicate` enum. In-Toto v1 statement deserialization and the CLI `--predicate` option
+//! both consult the registry. The predicate types supported by Spector are registered in
+//! the same registry.
+
+use anyhow::{anyhow, Result};
+use schemars::{schema::RootSchema, schema_for, JsonSchema};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::Track;
+use std::{
+    any::Any,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use super::{
+    predicate::{Predicate, RegisteredPredicate},
+    provenance::{SLSAProvenanceV1Predicate, SLSA_PROVENANCE_V1_PREDICATE_TYPE},
+    provenance_v02::{SLSAProvenanceV02Predicate, SLSA_PROVENANCE_V02_PREDICATE_TYPE},
+    vsa::{SLSAVerificationSummaryV1Predicate, SLSA_VSA_V1_PREDICATE_TYPE},
+};
+
+// A function deserializing a predicate JSON value into a Predicate, tracking the path of
+// any error within the predicate.
+type DeserializeFn = dyn Fn(&Value) -> Result<Predicate, PathError> + Send + Sync;
+
+/// A deserialization error along with the path within the predicate at which it occurred.
+pub type PathError = serde_path_to_error::Error<serde_json::Error>;
+
+/// A predicate type registered in the registry.
+#[derive(Clone)]
+pub struct PredicateRegistration {
+    /// The predicate type URI, e.g. `https://slsa.dev/provenance/v1`.
+    pub predicate_type: String,
+    /// A human readable name, e.g. `SLSAProvenanceV1`.
+    pub name: String,
+    /// A short alias used to select the predicate type on the command line,
+    /// e.g. `slsa-provenance-v1`.
+    pub alias: String,
+    /// The JSON schema of the predicate.
+    pub schema: RootSchema,
+    deserialize: Arc<DeserializeFn>,
+}
+
+impl PredicateRegistration {
+    /// Deserializes a predicate JSON value of this predicate type.
+    pub fn deserialize(&self, predicate: &Value) -> Result<Predicate, serde_json::Error> {
+        (self.deserialize)(predicate).map_err(PathError::into_inner)
+    }
+
+    /// Deserializes a predicate JSON value of this predicate type, reporting the path of
+    /// any error within the predicate.
+    ///
+    /// The path is only tracked for predicate types registered with `register` and the
+    /// built-in predicate types, and is empty otherwise.
+    pub fn deserialize_with_path(&self, predicate: &Value) -> Result<Predicate, PathError> {
+        (self.deserialize)(predicate)
+    }
+
+    /// Returns true if the name, alias or predicate type URI of this registration equals `key`.
+    pub fn matches(&self, key: &str) -> bool {
+        self.predicate_type == key || self.alias == key || self.name == key
+    }
+}
+
+impl std::fmt::Debug for PredicateRegistration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PredicateRegistration")
+            .field("predicate_type", &self.predicate_type)
+            .field("name", &self.name)
+            .field("alias", &self.alias)
+            .finish_non_exhaustive()
+    }
+}
+
+// Returns the global registry, registering the built-in predicate types on first use.
+// Registrations are shared, so that looking one up doesn't copy its schema.
+fn registry() -> &'static RwLock<Vec<Arc<PredicateRegistration>>> {
+    static REGISTRY: OnceLock<RwLock<Vec<Arc<PredicateRegistration>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(builtin_predicates().into_iter().map(Arc::new).collect()))
+}
+
+// The predicate types supported by Spector.
+fn builtin_predicates() -> Vec<PredicateRegistration> {
+    vec![
+        PredicateRegistration {
+            predicate_type: SLSA_PROVENANCE_V1_PREDICATE_TYPE.to_string(),
+            name: "SLSAProvenanceV1".to_string(),
+            alias: "slsa-provenance-v1".to_string(),
+            schema: schema_for!(SLSAProvenanceV1Predicate),
+            deserialize: Arc::new(|predicate| {
+                serde_path_to_error::deserialize::<_, SLSAProvenanceV1Predicate>(predicate)
+                    .map(Predicate::SLSAProvenanceV1)
+            }),
+        },
+        PredicateRegistration {
+            predicate_type: SLSA_PROVENANCE_V02_PREDICATE_TYPE.to_string(),
+            name: "SLSAProvenanceV02".to_string(),
+            alias: "slsa-provenance-v0.2".to_string(),
+            schema: schema_for!(SLSAProvenanceV02Predicate),
+            deserialize: Arc::new(|predicate| {
+                serde_path_to_error::deserialize::<_, SLSAProvenanceV02Predicate>(predicate)
+                    .map(Predicate::SLSAProvenanceV02)
+            }),
+        },
+        PredicateRegistration {
+            predicate_type: SLSA_VSA_V1_PREDICATE_TYPE.to_string(),
+            name: "SLSAVerificationSummaryV1".to_string(),
+            alias: "slsa-vsa-v1".to_string(),
+            schema: schema_for!(SLSAVerificationSummaryV1Predicate),
+            deserialize: Arc::new(|predicate| {
+                serde_path_to_error::deserialize::<_, SLSAVerificationSummaryV1Predicate>(predicate)
+                    .map(Predicate::SLSAVerificationSummaryV1)
+            }),
+        },
+    ]
+}
+
+/// Registers a predicate type, deserialized into `T`.
+///
+/// Statements with the given predicate type will have their predicate deserialized into
+/// `T` and stored as a `Predicate::Registered`, from which it can be retrieved with
+/// `RegisteredPredicate::downcast_ref`. Fails if the predicate type URI, name or alias is
+/// already registered.
+pub fn register<T>(predicate_type: &str, name: &str, alias: &str) -> Result<()>
+where
+    T: DeserializeOwned + JsonSchema + Send + Sync + 'static,
+{
+    let uri = predicate_type.to_string();
+    register_registration(
+        predicate_type,
+        name,
+        alias,
+        schema_for!(T),
+        Arc::new(move |predicate| {
+            let typed = serde_path_to_error::deserialize::<_, T>(predicate)?;
+            Ok(Predicate::Registered(RegisteredPredicate::new(
+                uri.clone(),
+                predicate.clone(),
+                Arc::new(typed) as Arc<dyn Any + Send + Sync>,
+            )))
+        }),
+    )
+}
+
+/// Registers a predicate type with a custom deserializer and schema.
+///
+/// This is the lower level counterpart of `register` for predicate types that can't be
+/// deserialized with a plain `serde` derive. Fails if the predicate type URI, name or alias
+/// is already registered.
+pub fn register_with<F>(
+    predicate_type: &str,
+    name: &str,
+    alias: &str,
+    schema: RootSchema,
+    deserialize: F,
+) -> Result<()>
+where
+    F: Fn(&Value) -> Result<Predicate, serde_json::Error> + Send + Sync + 'static,
+{
+    register_registration(
+        predicate_type,
+        name,
+        alias,
+        schema,
+        Arc::new(move |predicate| {
+            deserialize(predicate)
+                .map_err(|e| serde_path_to_error::Error::new(Track::new().path(), e))
+        }),
+    )
+}
+
+// Registers a predicate type, failing if its URI, name or alias is already registered.
+fn register_registration(
+    predicate_type: &str,
+    name: &str,
+    alias: &str,
+    schema: RootSchema,
+    deserialize: Arc<DeserializeFn>,
+) -> Result<()> {
+    let mut registrations = registry()
+        .write()
+        .map_err(|_| anyhow!("Predicate registry lock poisoned"))?;
+    for key in [predicate_type, name, alias] {
+        if let Some(existing) = registrations.iter().find(|r| r.matches(key)) {
+            return Err(anyhow!(
+                "Predicate {:?} conflicts with registered predicate type {:?}",
+                key,
+                existing.predicate_type
+            ));
+        }
+    }
+
+    registrations.push(Arc::new(PredicateRegistration {
+        predicate_type: predicate_type.to_string(),
+        name: name.to_string(),
+        alias: alias.to_string(),
+        schema,
+        deserialize,
+    }));
+    Ok(())
+}
+
+/// Looks up a registered predicate type by its predicate type URI, name or alias.
+pub fn lookup(key: &str) -> Option<Arc<PredicateRegistration>> {
+    registry()
+        .read()
+        .ok()?
+        .iter()
+        .find(|r| r.matches(key))
+        .cloned()
+}
+
+/// Returns all registered predicate types, in registration order.
+pub fn registrations() -> Vec<Arc<PredicateRegistration>> {
+    registry().read().map(|r| r.clone()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+    struct TestPredicate {
+        name: String,
+    }
+
+    #[test]
+    fn lookup_builtin_predicate() {
+        for key in [
+            "https://slsa.dev/provenance/v1",
+            "SLSAProvenanceV1",
+            "slsa-provenance-v1",
+        ] {
+            let registration = lookup(key).unwrap();
+            assert_eq!(registration.name, "SLSAProvenanceV1");
+        }
+        assert!(lookup("unknown").is_none());
+    }
+
+    #[test]
+    fn register_custom_predicate() {
+        register::<TestPredicate>(
+            "https://example.com/registry-test/v1",
+            "RegistryTestV1",
+            "registry-test-v1",
+        )
+        .unwrap();
+
+        let registration = lookup("registry-test-v1").unwrap();
+        let predicate = registration.deserialize(&json!({"name": "test"})).unwrap();
+        match predicate {
+            Predicate::Registered(registered) => {
+                assert_eq!(
+                    registered.predicate_type(),
+                    "https://example.com/registry-test/v1"
+                );
+                assert_eq!(
+                    registered.downcast_ref::<TestPredicate>(),
+                    Some(&TestPredicate {
+                        name: "test".to_string()
+                    })
+                );
+            }
+            other => panic!("Unexpected predicate: {:?}", other),
+        }
+
+        assert!(registration.deserialize(&json!({"other": 1})).is_err());
+        assert!(registrations().iter().any(|r| r.name == "RegistryTestV1"));
+    }
+
+    #[test]
+    fn register_conflicting_predicate() {
+        let result = register::<TestPredicate>(
+            "https://slsa.dev/provenance/v1",
+            "ConflictTest",
+            "conflict-test",
+        );
+        assert!(result.is_err());
+
+        let result = register::<TestPredicate>(
+            "https://example.com/conflict-test/v1",
+            "ConflictTest",
+            "slsa-provenance-v1",
+        );
+        assert!(result.is_err());
+        assert!(lookup("ConflictTest").is_none());
+    }
+}
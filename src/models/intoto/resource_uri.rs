@@ -0,0 +1,367 @@
+//! The URI of an in-toto resource descriptor.
+//!
+//! Resource descriptor URIs aren't limited to URLs: they are commonly package URLs, e.g.
+//! `pkg:npm/foo@1.0`, VCS URIs, e.g. `git+https://github.com/octocat/hello-world@refs/heads/main`,
+//! or relative references. Package URLs and VCS URIs are parsed into structured forms so
+//! their components can be inspected.
+
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Schema, SchemaObject},
+    JsonSchema,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+use url::Url;
+
+/// The version control systems recognized in VCS URIs.
+const VCS_SCHEMES: [&str; 4] = ["git", "hg", "svn", "bzr"];
+
+/// The URI of a resource descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    /// A package URL, e.g. `pkg:npm/foo@1.0`.
+    Purl(Purl),
+    /// A VCS URI, e.g. `git+https://github.com/octocat/hello-world@refs/heads/main`.
+    Vcs(VcsUri),
+    /// Any other absolute URI.
+    Url(Url),
+    /// A relative reference, e.g. `dist/hello.tar.gz`.
+    Relative(String),
+}
+
+/// A package URL, see <https://github.com/package-url/purl-spec>.
+///
+/// Components are kept percent-encoded as they appear in the URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Purl {
+    /// The package type, e.g. `npm` or `maven`.
+    pub package_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    /// The qualifiers, in the order they appear in the URI.
+    pub qualifiers: Vec<(String, String)>,
+    pub subpath: Option<String>,
+}
+
+/// A VCS URI, as used by SPDX download locations and SLSA resolved dependencies, e.g.
+/// `git+https://github.com/octocat/hello-world@refs/heads/main#subdir`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VcsUri {
+    /// The version control system, e.g. `git`.
+    pub vcs: String,
+    /// The repository URL, without the revision and subpath.
+    pub repository: Url,
+    /// The revision, e.g. a ref or commit.
+    pub revision: Option<String>,
+    pub subpath: Option<String>,
+}
+
+impl FromStr for Purl {
+    type Err = String;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let error = |reason: &str| format!("invalid package URL {:?}: {}", uri, reason);
+        let rest = uri
+            .strip_prefix("pkg:")
+            .ok_or_else(|| error("expected the pkg scheme"))?
+            .trim_start_matches('/');
+
+        let (rest, subpath) = split_once_opt(rest, '#');
+        let (rest, qualifiers) = split_once_opt(rest, '?');
+        let (path, version) = match rest.rfind('@') {
+            Some(index) => (&rest[..index], Some(rest[index + 1..].to_string())),
+            None => (rest, None),
+        };
+
+        let mut segments = path.trim_end_matches('/').split('/');
+        let package_type = segments.next().unwrap_or_default();
+        if package_type.is_empty() {
+            return Err(error("missing package type"));
+        }
+        let mut segments = segments.collect::<Vec<_>>();
+        let name = match segments.pop() {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => return Err(error("missing package name")),
+        };
+        let namespace = if segments.is_empty() {
+            None
+        } else {
+            Some(segments.join("/"))
+        };
+
+        let qualifiers = match qualifiers {
+            Some(qualifiers) => qualifiers
+                .split('&')
+                .map(|qualifier| match qualifier.split_once('=') {
+                    Some((key, value)) if !key.is_empty() => {
+                        Ok((key.to_string(), value.to_string()))
+                    }
+                    _ => Err(error(&format!("invalid qualifier {:?}", qualifier))),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(Purl {
+            package_type: package_type.to_string(),
+            namespace,
+            name,
+            version,
+            qualifiers,
+            subpath: subpath.map(str::to_string),
+        })
+    }
+}
+
+impl std::fmt::Display for Purl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "pkg:{}/", self.package_type)?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, "{}/", namespace)?;
+        }
+        f.write_str(&self.name)?;
+        if let Some(version) = &self.version {
+            write!(f, "@{}", version)?;
+        }
+        for (index, (key, value)) in self.qualifiers.iter().enumerate() {
+            let separator = if index == 0 { '?' } else { '&' };
+            write!(f, "{}{}={}", separator, key, value)?;
+        }
+        if let Some(subpath) = &self.subpath {
+            write!(f, "#{}", subpath)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for VcsUri {
+    type Err = String;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let error = |reason: String| format!("invalid VCS URI {:?}: {}", uri, reason);
+        let (vcs, rest) = uri
+            .split_once('+')
+            .filter(|(vcs, _)| VCS_SCHEMES.contains(vcs))
+            .ok_or_else(|| error("expected a <vcs>+<url> URI".to_string()))?;
+
+        let (rest, subpath) = split_once_opt(rest, '#');
+        // The revision follows the last `@` in the path, as an `@` before the path separates
+        // the user info, e.g. in `git+ssh://git@github.com/octocat/hello-world`.
+        let path_start = rest
+            .find("://")
+            .map(|index| index + 3)
+            .and_then(|authority| rest[authority..].find('/').map(|path| authority + path))
+            .unwrap_or(rest.len());
+        let (repository, revision) = match rest[path_start..].rfind('@') {
+            Some(index) => (
+                &rest[..path_start + index],
+                Some(rest[path_start + index + 1..].to_string()),
+            ),
+            None => (rest, None),
+        };
+
+        Ok(VcsUri {
+            vcs: vcs.to_string(),
+            repository: Url::parse(repository).map_err(|e| error(e.to_string()))?,
+            revision,
+            subpath: subpath.map(str::to_string),
+        })
+    }
+}
+
+impl std::fmt::Display for VcsUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}+{}", self.vcs, self.repository)?;
+        if let Some(revision) = &self.revision {
+            write!(f, "@{}", revision)?;
+        }
+        if let Some(subpath) = &self.subpath {
+            write!(f, "#{}", subpath)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ResourceUri {
+    type Err = String;
+
+    /// Parses a URI, recognizing package URLs and VCS URIs, and falling back to a relative
+    /// reference for anything that isn't an absolute URI.
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        if uri.is_empty() {
+            return Err("invalid URI: expected a non-empty URI".to_string());
+        }
+        if uri.starts_with("pkg:") {
+            return uri.parse().map(ResourceUri::Purl);
+        }
+        if uri
+            .split_once('+')
+            .is_some_and(|(vcs, _)| VCS_SCHEMES.contains(&vcs))
+        {
+            return uri.parse().map(ResourceUri::Vcs);
+        }
+        match Url::parse(uri) {
+            Ok(url) => Ok(ResourceUri::Url(url)),
+            Err(url::ParseError::RelativeUrlWithoutBase) => {
+                Ok(ResourceUri::Relative(uri.to_string()))
+            }
+            Err(e) => Err(format!("invalid URI {:?}: {}", uri, e)),
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceUri::Purl(purl) => purl.fmt(f),
+            ResourceUri::Vcs(vcs) => vcs.fmt(f),
+            ResourceUri::Url(url) => f.write_str(url.as_str()),
+            ResourceUri::Relative(reference) => f.write_str(reference),
+        }
+    }
+}
+
+impl From<Url> for ResourceUri {
+    /// Converts a URL, recognizing package URLs and VCS URIs.
+    fn from(url: Url) -> Self {
+        url.as_str().parse().unwrap_or(ResourceUri::Url(url))
+    }
+}
+
+impl Serialize for ResourceUri {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResourceUri {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let uri = String::deserialize(deserializer)?;
+        uri.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for ResourceUri {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "ResourceUri".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some("uri-reference".to_string()),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+// Splits a string at the first occurrence of a delimiter, if any.
+fn split_once_opt(s: &str, delimiter: char) -> (&str, Option<&str>) {
+    match s.split_once(delimiter) {
+        Some((before, after)) => (before, Some(after)),
+        None => (s, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_purl() {
+        let uri = "pkg:maven/org.apache.commons/io@1.3.4?type=jar&classifier=sources#src/main";
+        let purl = match uri.parse::<ResourceUri>().unwrap() {
+            ResourceUri::Purl(purl) => purl,
+            other => panic!("expected a purl, found {:?}", other),
+        };
+        assert_eq!(purl.package_type, "maven");
+        assert_eq!(purl.namespace.as_deref(), Some("org.apache.commons"));
+        assert_eq!(purl.name, "io");
+        assert_eq!(purl.version.as_deref(), Some("1.3.4"));
+        assert_eq!(
+            purl.qualifiers,
+            vec![
+                ("type".to_string(), "jar".to_string()),
+                ("classifier".to_string(), "sources".to_string())
+            ]
+        );
+        assert_eq!(purl.subpath.as_deref(), Some("src/main"));
+        assert_eq!(purl.to_string(), uri);
+
+        let purl = "pkg:npm/%40angular/animation@12.3.1"
+            .parse::<Purl>()
+            .unwrap();
+        assert_eq!(purl.namespace.as_deref(), Some("%40angular"));
+        assert_eq!(purl.name, "animation");
+
+        assert!("pkg:npm".parse::<ResourceUri>().is_err());
+        assert!("pkg:npm/foo?bar".parse::<ResourceUri>().is_err());
+    }
+
+    #[test]
+    fn parse_vcs_uri() {
+        let uri = "git+https://github.com/octocat/hello-world@refs/heads/main#subdir";
+        let vcs = match uri.parse::<ResourceUri>().unwrap() {
+            ResourceUri::Vcs(vcs) => vcs,
+            other => panic!("expected a VCS URI, found {:?}", other),
+        };
+        assert_eq!(vcs.vcs, "git");
+        assert_eq!(
+            vcs.repository.as_str(),
+            "https://github.com/octocat/hello-world"
+        );
+        assert_eq!(vcs.revision.as_deref(), Some("refs/heads/main"));
+        assert_eq!(vcs.subpath.as_deref(), Some("subdir"));
+        assert_eq!(vcs.to_string(), uri);
+
+        let vcs = "git+ssh://git@github.com/octocat/hello-world.git"
+            .parse::<VcsUri>()
+            .unwrap();
+        assert_eq!(vcs.repository.username(), "git");
+        assert_eq!(vcs.revision, None);
+
+        assert!("git+not a url".parse::<ResourceUri>().is_err());
+    }
+
+    #[test]
+    fn parse_url_and_relative_reference() {
+        assert_eq!(
+            "https://example.com/dependency1".parse::<ResourceUri>(),
+            Ok(ResourceUri::Url(
+                Url::parse("https://example.com/dependency1").unwrap()
+            ))
+        );
+        assert_eq!(
+            "dist/hello.tar.gz".parse::<ResourceUri>(),
+            Ok(ResourceUri::Relative("dist/hello.tar.gz".to_string()))
+        );
+        assert!("".parse::<ResourceUri>().is_err());
+        assert!("https://exa mple.com".parse::<ResourceUri>().is_err());
+    }
+
+    #[test]
+    fn serialize_resource_uri_roundtrip() {
+        for uri in [
+            "pkg:npm/foo@1.0",
+            "git+https://github.com/octocat/hello-world@refs/heads/main",
+            "https://example.com/dependency1",
+            "hello.txt",
+        ] {
+            let parsed: ResourceUri = serde_json::from_value(serde_json::json!(uri)).unwrap();
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), uri);
+        }
+    }
+}
@@ -2,12 +2,13 @@
 //!
 //! This module provides the InTotoStatementV1 struct, as well as related structures for
 //! subjects, algorithms, and digest sets. It also includes custom (de)serialization
-//! code for handling In-Toto v1 statements.
+//! code for handling In-Toto v1 statements, validating digests against their algorithm.
 
-use schemars::JsonSchema;
-use serde::{Deserialize, Deserializer, Serialize};
+use base64::{engine::general_purpose, Engine};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 use url::Url;
 
 use crate::models::{
@@ -28,11 +29,13 @@ pub struct InTotoStatementV1 {
     pub predicate: Predicate,
 }
 
-/// Enum for the supported hashing algorithms.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
-#[serde(rename_all = "lowercase")]
+/// Enum for the digest algorithms of a DigestSet.
+///
+/// Algorithms listed in the In-Toto DigestSet specification have their own variants, while
+/// any other lowercase algorithm name is represented by the `Custom` variant. `DigestSet::new`
+/// rejects invalid custom names and replaces those of listed algorithms by their variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Algorithm {
-    // TODO(mlieberman85): Add validation for the length/encoding of the digest string.
     Sha224,
     Sha256,
     Sha384,
@@ -52,48 +55,387 @@ pub enum Algorithm {
     Gost,
     Sha1,
     Md5,
+    GitCommit,
+    GitTree,
+    GitBlob,
+    GitTag,
+    DirHash,
+    Custom(String),
 }
 
+// The algorithms listed in the In-Toto DigestSet specification.
+const KNOWN_ALGORITHMS: [Algorithm; 24] = [
+    Algorithm::Sha224,
+    Algorithm::Sha256,
+    Algorithm::Sha384,
+    Algorithm::Sha512,
+    Algorithm::Sha512_224,
+    Algorithm::Sha512_256,
+    Algorithm::Sha3_224,
+    Algorithm::Sha3_256,
+    Algorithm::Sha3_384,
+    Algorithm::Sha3_512,
+    Algorithm::Shake128,
+    Algorithm::Shake256,
+    Algorithm::Blake2b,
+    Algorithm::Blake2s,
+    Algorithm::Ripemd160,
+    Algorithm::Sm3,
+    Algorithm::Gost,
+    Algorithm::Sha1,
+    Algorithm::Md5,
+    Algorithm::GitCommit,
+    Algorithm::GitTree,
+    Algorithm::GitBlob,
+    Algorithm::GitTag,
+    Algorithm::DirHash,
+];
+
+// The rule custom algorithm names must follow, as reported in errors.
+const CUSTOM_ALGORITHM_RULE: &str =
+    "custom algorithms must start with a lowercase letter and contain only lowercase letters, \
+     digits, `_` and `-`";
+
+impl Algorithm {
+    /// Returns the name of the algorithm as used in a DigestSet.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Algorithm::Sha224 => "sha224",
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha384 => "sha384",
+            Algorithm::Sha512 => "sha512",
+            Algorithm::Sha512_224 => "sha512_224",
+            Algorithm::Sha512_256 => "sha512_256",
+            Algorithm::Sha3_224 => "sha3_224",
+            Algorithm::Sha3_256 => "sha3_256",
+            Algorithm::Sha3_384 => "sha3_384",
+            Algorithm::Sha3_512 => "sha3_512",
+            Algorithm::Shake128 => "shake128",
+            Algorithm::Shake256 => "shake256",
+            Algorithm::Blake2b => "blake2b",
+            Algorithm::Blake2s => "blake2s",
+            Algorithm::Ripemd160 => "ripemd160",
+            Algorithm::Sm3 => "sm3",
+            Algorithm::Gost => "gost",
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Md5 => "md5",
+            Algorithm::GitCommit => "gitCommit",
+            Algorithm::GitTree => "gitTree",
+            Algorithm::GitBlob => "gitBlob",
+            Algorithm::GitTag => "gitTag",
+            Algorithm::DirHash => "dirHash",
+            Algorithm::Custom(name) => name,
+        }
+    }
+
+    /// Returns the accepted lengths in hex characters of a digest for this algorithm.
+    ///
+    /// `None` means the algorithm has a variable output length, in which case any
+    /// non-empty whole number of bytes is accepted.
+    fn hex_lengths(&self) -> Option<&'static [usize]> {
+        match self {
+            Algorithm::Sha224 | Algorithm::Sha512_224 | Algorithm::Sha3_224 => Some(&[56]),
+            Algorithm::Sha256
+            | Algorithm::Sha512_256
+            | Algorithm::Sha3_256
+            | Algorithm::Sm3
+            | Algorithm::DirHash => Some(&[64]),
+            Algorithm::Sha384 | Algorithm::Sha3_384 => Some(&[96]),
+            Algorithm::Sha512 | Algorithm::Sha3_512 => Some(&[128]),
+            Algorithm::Sha1 | Algorithm::Ripemd160 => Some(&[40]),
+            Algorithm::Md5 => Some(&[32]),
+            Algorithm::Gost => Some(&[64, 128]),
+            // Git object IDs are SHA-1, or SHA-256 for repositories using the sha256 object format.
+            Algorithm::GitCommit | Algorithm::GitTree | Algorithm::GitBlob | Algorithm::GitTag => {
+                Some(&[40, 64])
+            }
+            Algorithm::Shake128
+            | Algorithm::Shake256
+            | Algorithm::Blake2b
+            | Algorithm::Blake2s
+            | Algorithm::Custom(_) => None,
+        }
+    }
+
+    /// Validates a digest for this algorithm, returning it normalized.
+    ///
+    /// Hex-encoded digests are normalized to lowercase. `dirHash` additionally accepts the
+    /// Go module `h1:<base64>` encoding, and custom algorithms accept any non-empty digest.
+    pub fn validate_digest(&self, digest: &str) -> Result<String, DigestError> {
+        let error = |reason: String| DigestError {
+            algorithm: self.clone(),
+            digest: digest.to_string(),
+            reason,
+        };
+
+        match self {
+            Algorithm::Custom(_) if digest.is_empty() => {
+                return Err(error("expected a non-empty digest".to_string()));
+            }
+            Algorithm::Custom(_) => return Ok(digest.to_string()),
+            Algorithm::DirHash if digest.starts_with("h1:") => {
+                return match general_purpose::STANDARD.decode(&digest[3..]) {
+                    Ok(bytes) if bytes.len() == 32 => Ok(digest.to_string()),
+                    _ => Err(error(
+                        "expected \"h1:\" followed by a base64-encoded SHA-256 digest".to_string(),
+                    )),
+                };
+            }
+            _ => {}
+        }
+
+        if !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error("expected hexadecimal characters".to_string()));
+        }
+        match self.hex_lengths() {
+            Some(lengths) if !lengths.contains(&digest.len()) => {
+                let expected = lengths
+                    .iter()
+                    .map(|l| l.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" or ");
+                return Err(error(format!(
+                    "expected {} hexadecimal characters, found {}",
+                    expected,
+                    digest.len()
+                )));
+            }
+            None if digest.is_empty() || !digest.len().is_multiple_of(2) => {
+                return Err(error(format!(
+                    "expected a non-empty, even number of hexadecimal characters, found {}",
+                    digest.len()
+                )));
+            }
+            _ => {}
+        }
+        Ok(digest.to_ascii_lowercase())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    /// Parses an algorithm name, falling back to a custom algorithm for unknown names.
+    ///
+    /// Custom algorithm names must start with a lowercase letter and only contain lowercase
+    /// letters, digits, `_` and `-`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if let Some(algorithm) = KNOWN_ALGORITHMS.iter().find(|a| a.as_str() == name) {
+            return Ok(algorithm.clone());
+        }
+
+        let mut chars = name.chars();
+        let valid = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+        if valid {
+            Ok(Algorithm::Custom(name.to_string()))
+        } else {
+            Err(format!(
+                "invalid digest algorithm {:?}: {}",
+                name, CUSTOM_ALGORITHM_RULE
+            ))
+        }
+    }
+}
+
+impl std::fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Algorithm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Algorithm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for Algorithm {
+    fn schema_name() -> String {
+        "Algorithm".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+/// An error describing a digest that is invalid for its algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestError {
+    pub algorithm: Algorithm,
+    pub digest: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for DigestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid {} digest {:?}: {}",
+            self.algorithm, self.digest, self.reason
+        )
+    }
+}
+
+impl std::error::Error for DigestError {}
+
 /// Represents a set of digests, mapping algorithms to their respective digest strings.
-#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(try_from = "HashMap<Algorithm, String>")]
 pub struct DigestSet(HashMap<Algorithm, String>);
 
+impl DigestSet {
+    /// Creates a new DigestSet, validating and normalizing each digest.
+    ///
+    /// Custom algorithm names are validated like deserialized ones, and the names of listed
+    /// algorithms, e.g. `Custom("sha256")`, are replaced by their variant. Fails if an
+    /// algorithm is then given twice.
+    pub fn new(digests: HashMap<Algorithm, String>) -> Result<Self, DigestError> {
+        let mut set = HashMap::with_capacity(digests.len());
+        for (algorithm, digest) in digests {
+            let error = |algorithm: Algorithm, reason: String| DigestError {
+                algorithm,
+                digest: digest.clone(),
+                reason,
+            };
+            let algorithm = match algorithm {
+                Algorithm::Custom(name) => match name.parse::<Algorithm>() {
+                    Ok(algorithm) => algorithm,
+                    Err(_) => {
+                        return Err(error(
+                            Algorithm::Custom(name),
+                            CUSTOM_ALGORITHM_RULE.to_string(),
+                        ))
+                    }
+                },
+                algorithm => algorithm,
+            };
+            if set.contains_key(&algorithm) {
+                return Err(error(algorithm, "algorithm given twice".to_string()));
+            }
+            let digest = algorithm.validate_digest(&digest)?;
+            set.insert(algorithm, digest);
+        }
+        Ok(DigestSet(set))
+    }
+
+    /// Returns the digest for the given algorithm, if present.
+    pub fn get(&self, algorithm: &Algorithm) -> Option<&str> {
+        self.0.get(algorithm).map(String::as_str)
+    }
+
+    /// Returns an iterator over the algorithms and digests in the set.
+    pub fn iter(&self) -> impl Iterator<Item = (&Algorithm, &str)> {
+        self.0.iter().map(|(a, d)| (a, d.as_str()))
+    }
+}
+
+impl TryFrom<HashMap<Algorithm, String>> for DigestSet {
+    type Error = DigestError;
+
+    fn try_from(digests: HashMap<Algorithm, String>) -> Result<Self, Self::Error> {
+        DigestSet::new(digests)
+    }
+}
+
 /// Represents a subject in an In-Toto v1 statement.
-#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Subject {
     pub name: String,
     pub digest: DigestSet,
 }
 
-// Custom deserialization for InTotoStatementV1.
-impl<'de> Deserialize<'de> for InTotoStatementV1 {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        // Helper struct to deserialize the JSON before constructing the InTotoStatementV1.
-        #[derive(Deserialize)]
-        struct Helper {
-            #[serde(rename = "_type", with = "url_serde")]
-            _type: Url,
-            subject: Vec<Subject>,
-            #[serde(rename = "predicateType", with = "url_serde")]
-            predicate_type: Url,
-            predicate: Value,
+/// The `_type` of an In-Toto v1 statement.
+pub const STATEMENT_TYPE_V1: &str = "https://in-toto.io/Statement/v1";
+
+/// The `_type` of a legacy In-Toto v0.1 statement, only accepted when deserializing leniently.
+pub const STATEMENT_TYPE_V01: &str = "https://in-toto.io/Statement/v0.1";
+
+// Helper struct to deserialize the JSON before constructing the InTotoStatementV1.
+#[derive(Deserialize)]
+struct Helper {
+    #[serde(rename = "_type", with = "url_serde")]
+    _type: Url,
+    // Subjects are deserialized individually so errors can report the offending subject index.
+    subject: Vec<Value>,
+    #[serde(rename = "predicateType", with = "url_serde")]
+    predicate_type: Url,
+    predicate: Value,
+}
+
+impl InTotoStatementV1 {
+    /// Deserializes a statement from a JSON value, additionally accepting the legacy
+    /// `https://in-toto.io/Statement/v0.1` `_type`.
+    ///
+    /// Returns the statement along with any warnings, e.g. when the legacy `_type` was used.
+    pub fn from_value_lenient(value: &Value) -> Result<(Self, Vec<String>), serde_json::Error> {
+        let helper = Helper::deserialize(value)?;
+        Self::from_helper(helper, true).map_err(serde::de::Error::custom)
+    }
+
+    // Validates the helper and constructs the statement, deserializing the predicate based on
+    // the predicate type.
+    fn from_helper(helper: Helper, lenient: bool) -> Result<(Self, Vec<String>), String> {
+        let mut warnings = Vec::new();
+        match helper._type.as_str() {
+            STATEMENT_TYPE_V1 => {}
+            STATEMENT_TYPE_V01 if lenient => warnings.push(format!(
+                "Legacy _type {:?} accepted, expected {:?}",
+                STATEMENT_TYPE_V01, STATEMENT_TYPE_V1
+            )),
+            other => {
+                return Err(format!(
+                    "Invalid _type: {:?}, expected {:?}",
+                    other, STATEMENT_TYPE_V1
+                ))
+            }
         }
 
-        let helper = Helper::deserialize(deserializer)?;
+        let subject = helper
+            .subject
+            .into_iter()
+            .enumerate()
+            .map(|(index, subject)| {
+                Subject::deserialize(subject).map_err(|e| format!("subject[{}]: {}", index, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        // Deserialize the predicate based on the predicate type.
-        let predicate = deserialize_predicate(&helper.predicate_type.as_str(), &helper.predicate)
-            .map_err(serde::de::Error::custom)?;
+        let predicate = deserialize_predicate(helper.predicate_type.as_str(), &helper.predicate)
+            .map_err(|e| e.to_string())?;
 
-        Ok(InTotoStatementV1 {
+        let statement = InTotoStatementV1 {
             _type: helper._type,
-            subject: helper.subject,
+            subject,
             predicate_type: helper.predicate_type,
             predicate,
-        })
+        };
+        Ok((statement, warnings))
+    }
+}
+
+// Custom deserialization for InTotoStatementV1.
+impl<'de> Deserialize<'de> for InTotoStatementV1 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let helper = Helper::deserialize(deserializer)?;
+        let (statement, _) = Self::from_helper(helper, false).map_err(serde::de::Error::custom)?;
+        Ok(statement)
     }
 }
 
@@ -111,7 +453,7 @@ mod tests {
                 {
                     "name": "example",
                     "digest": {
-                        "sha256": "abcd1234"
+                        "sha256": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"
                     }
                 }
             ]
@@ -135,7 +477,7 @@ mod tests {
                 {
                     "name": "example",
                     "digest": {
-                        "sha256": "abcd1234"
+                        "sha256": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"
                     }
                 }
             ]
@@ -158,7 +500,7 @@ mod tests {
                 {
                     "name": "example",
                     "digest": {
-                        "sha256": "abcd1234"
+                        "sha256": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"
                     }
                 }
             ]
@@ -195,7 +537,7 @@ mod tests {
                 {
                     "name": "example",
                     "digest": {
-                        "sha256": "abcd1234"
+                        "sha256": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"
                     }
                 }
             ]
@@ -217,7 +559,7 @@ mod tests {
                 {
                     "name": "example",
                     "digest": {
-                        "sha256": "abcd1234"
+                        "sha256": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"
                     }
                 }
             ]
@@ -252,4 +594,220 @@ mod tests {
             "Deserialization should fail due to invalid digest in the subject"
         );
     }
+
+    #[test]
+    fn deserialize_intoto_statement_invalid_type_message() {
+        let json_data = r#"{
+            "_type": "https://example.com/invalid",
+            "predicateType": "https://random.type/predicate/v1",
+            "predicate": {},
+            "subject": []
+        }"#;
+
+        let result: Result<InTotoStatementV1, _> = serde_json::from_str(json_data);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid _type: \"https://example.com/invalid\""));
+    }
+
+    #[test]
+    fn deserialize_intoto_statement_legacy_type_strict() {
+        let json_data = r#"{
+            "_type": "https://in-toto.io/Statement/v0.1",
+            "predicateType": "https://random.type/predicate/v1",
+            "predicate": {},
+            "subject": []
+        }"#;
+
+        let result: Result<InTotoStatementV1, _> = serde_json::from_str(json_data);
+        assert!(
+            result.is_err(),
+            "Strict deserialization should reject the legacy _type"
+        );
+    }
+
+    #[test]
+    fn deserialize_intoto_statement_legacy_type_lenient() {
+        let json_data = serde_json::json!({
+            "_type": "https://in-toto.io/Statement/v0.1",
+            "predicateType": "https://random.type/predicate/v1",
+            "predicate": {},
+            "subject": []
+        });
+
+        let (statement, warnings) = InTotoStatementV1::from_value_lenient(&json_data).unwrap();
+        assert_eq!(statement._type.as_str(), STATEMENT_TYPE_V01);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Legacy _type"));
+    }
+
+    #[test]
+    fn deserialize_intoto_statement_lenient_still_rejects_invalid_type() {
+        let json_data = serde_json::json!({
+            "_type": "https://example.com/invalid",
+            "predicateType": "https://random.type/predicate/v1",
+            "predicate": {},
+            "subject": []
+        });
+
+        let result = InTotoStatementV1::from_value_lenient(&json_data);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deserialize_intoto_statement_lenient_v1_has_no_warnings() {
+        let json_data = serde_json::json!({
+            "_type": "https://in-toto.io/Statement/v1",
+            "predicateType": "https://random.type/predicate/v1",
+            "predicate": {},
+            "subject": []
+        });
+
+        let (_, warnings) = InTotoStatementV1::from_value_lenient(&json_data).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn deserialize_intoto_statement_invalid_subject_message() {
+        let json_data = r#"{
+            "_type": "https://in-toto.io/Statement/v1",
+            "predicateType": "https://random.type/predicate/v1",
+            "predicate": {},
+            "subject": [
+                {
+                    "name": "valid",
+                    "digest": {
+                        "sha1": "c27d339ee6075c1f744c5d4b200f7901aad2c369"
+                    }
+                },
+                {
+                    "name": "invalid",
+                    "digest": {
+                        "sha512": "abcd"
+                    }
+                }
+            ]
+        }"#;
+
+        let result: Result<InTotoStatementV1, _> = serde_json::from_str(json_data);
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("subject[1]"), "{}", message);
+        assert!(
+            message.contains("invalid sha512 digest \"abcd\""),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn digest_set_normalizes_to_lowercase() {
+        let digest_set: DigestSet = serde_json::from_str(
+            r#"{"sha256": "FE4FE40AC7250263C5DBE1CF3138912F3F416140AA248637A60D65FE22C47DA4"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            digest_set.get(&Algorithm::Sha256),
+            Some("fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4")
+        );
+    }
+
+    #[test]
+    fn digest_set_git_object_ids() {
+        let sha1_commit: Result<DigestSet, _> =
+            serde_json::from_str(r#"{"gitCommit": "c27d339ee6075c1f744c5d4b200f7901aad2c369"}"#);
+        assert!(sha1_commit.is_ok());
+
+        let sha256_commit: Result<DigestSet, _> = serde_json::from_str(
+            r#"{"gitCommit": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"}"#,
+        );
+        assert!(sha256_commit.is_ok());
+
+        let short_commit: Result<DigestSet, _> =
+            serde_json::from_str(r#"{"gitCommit": "c27d339"}"#);
+        assert!(short_commit.is_err());
+    }
+
+    #[test]
+    fn validate_digest_lengths() {
+        assert!(Algorithm::Md5
+            .validate_digest("d41d8cd98f00b204e9800998ecf8427e")
+            .is_ok());
+        assert!(Algorithm::Sha1
+            .validate_digest("d41d8cd98f00b204e9800998ecf8427e")
+            .is_err());
+        assert!(Algorithm::Blake2b.validate_digest("abcd").is_ok());
+        assert!(Algorithm::Blake2b.validate_digest("abc").is_err());
+        assert!(Algorithm::Shake256.validate_digest("").is_err());
+        assert!(Algorithm::Sha256
+            .validate_digest("zz4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4")
+            .is_err());
+    }
+
+    #[test]
+    fn digest_set_custom_and_spec_listed_keys() {
+        let digest_set: DigestSet = serde_json::from_str(
+            r#"{
+                "dirHash": "h1:47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=",
+                "gitTree": "c27d339ee6075c1f744c5d4b200f7901aad2c369",
+                "my-hash_v2": "anything"
+            }"#,
+        )
+        .unwrap();
+        assert!(digest_set.get(&Algorithm::DirHash).is_some());
+        assert!(digest_set.get(&Algorithm::GitTree).is_some());
+        assert_eq!(
+            digest_set.get(&Algorithm::Custom("my-hash_v2".to_string())),
+            Some("anything")
+        );
+
+        let serialized = serde_json::to_value(&digest_set).unwrap();
+        assert_eq!(serialized["my-hash_v2"], "anything");
+    }
+
+    #[test]
+    fn digest_set_invalid_custom_key() {
+        let result: Result<DigestSet, _> = serde_json::from_str(r#"{"MyHash": "abcd"}"#);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("invalid digest algorithm \"MyHash\""));
+
+        let result: Result<DigestSet, _> = serde_json::from_str(r#"{"custom": ""}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn digest_set_canonicalizes_custom_keys() {
+        let sha256 = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let digest_set = DigestSet::new(HashMap::from([(
+            Algorithm::Custom("sha256".to_string()),
+            sha256.to_string(),
+        )]))
+        .unwrap();
+        assert_eq!(digest_set.get(&Algorithm::Sha256), Some(sha256));
+
+        let err = DigestSet::new(HashMap::from([
+            (Algorithm::Custom("sha256".to_string()), sha256.to_string()),
+            (Algorithm::Sha256, sha256.to_string()),
+        ]))
+        .unwrap_err();
+        assert_eq!(err.algorithm, Algorithm::Sha256);
+        assert_eq!(err.reason, "algorithm given twice");
+
+        let err = DigestSet::new(HashMap::from([(
+            Algorithm::Custom("SHA 256".to_string()),
+            sha256.to_string(),
+        )]))
+        .unwrap_err();
+        assert_eq!(err.reason, CUSTOM_ALGORITHM_RULE);
+        assert!(err.reason.contains("start with a lowercase letter"));
+    }
+
+    #[test]
+    fn parse_known_algorithms() {
+        for algorithm in KNOWN_ALGORITHMS.iter() {
+            assert_eq!(&algorithm.as_str().parse::<Algorithm>().unwrap(), algorithm);
+        }
+    }
 }
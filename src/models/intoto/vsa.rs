@@ -0,0 +1,133 @@
+//! SLSA verification summary attestation (VSA) predicate model and associated structures.
+//!
+//! A VSA records that a verifier checked an artifact's attestations against a policy, and
+//! the SLSA levels it verified, so that consumers don't have to repeat the verification.
+
+use crate::models::{helpers::url_serde, intoto::provenance::ResourceDescriptor};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// The predicate type of the SLSA Verification Summary v1 Predicate.
+pub const SLSA_VSA_V1_PREDICATE_TYPE: &str = "https://slsa.dev/verification_summary/v1";
+
+/// A structure representing the SLSA Verification Summary v1 Predicate.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct SLSAVerificationSummaryV1Predicate {
+    pub verifier: Verifier,
+    #[serde(rename = "timeVerified")]
+    pub time_verified: DateTime<Utc>,
+    #[serde(rename = "resourceUri", with = "url_serde")]
+    #[schemars(with = "Url")]
+    pub resource_uri: Url,
+    pub policy: ResourceDescriptor,
+    #[serde(
+        rename = "inputAttestations",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub input_attestations: Option<Vec<ResourceDescriptor>>,
+    #[serde(rename = "verificationResult")]
+    pub verification_result: VerificationResult,
+    #[serde(rename = "verifiedLevels")]
+    pub verified_levels: Vec<String>,
+    #[serde(
+        rename = "dependencyLevels",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dependency_levels: Option<HashMap<String, u64>>,
+    #[serde(
+        rename = "slsaVersion",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub slsa_version: Option<String>,
+}
+
+/// A structure representing the verifier of the SLSA Verification Summary v1 Predicate.
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Verifier {
+    #[serde(with = "url_serde")]
+    #[schemars(with = "Url")]
+    pub id: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<HashMap<String, String>>,
+}
+
+/// The result of a verification summarized by the SLSA Verification Summary v1 Predicate.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum VerificationResult {
+    Passed,
+    Failed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn get_test_vsa_json() -> serde_json::Value {
+        json!({
+            "verifier": {
+                "id": "https://example.com/publication_verifier",
+                "version": {
+                    "slsa-verifier-linux-amd64": "v2.3.0"
+                }
+            },
+            "timeVerified": "2023-01-01T12:34:56Z",
+            "resourceUri": "pkg:npm/hello-world@1.0.0",
+            "policy": {
+                "uri": "https://example.com/example_tarball.policy",
+                "digest": {
+                    "sha256": "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4"
+                }
+            },
+            "inputAttestations": [
+                {
+                    "uri": "https://example.com/provenance/example.intoto.jsonl",
+                    "digest": {
+                        "sha256": "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03"
+                    }
+                }
+            ],
+            "verificationResult": "PASSED",
+            "verifiedLevels": ["SLSA_BUILD_LEVEL_3"],
+            "dependencyLevels": {
+                "SLSA_BUILD_LEVEL_3": 5,
+                "SLSA_BUILD_LEVEL_1": 1
+            },
+            "slsaVersion": "1.0"
+        })
+    }
+
+    #[test]
+    fn deserialize_vsa() {
+        let vsa: SLSAVerificationSummaryV1Predicate =
+            serde_json::from_value(get_test_vsa_json()).unwrap();
+
+        assert_eq!(vsa.verification_result, VerificationResult::Passed);
+        assert_eq!(vsa.verified_levels, vec!["SLSA_BUILD_LEVEL_3"]);
+        assert_eq!(vsa.resource_uri.as_str(), "pkg:npm/hello-world@1.0.0");
+        assert_eq!(vsa.dependency_levels.unwrap()["SLSA_BUILD_LEVEL_3"], 5);
+    }
+
+    #[test]
+    fn deserialize_vsa_invalid_result() {
+        let mut json_data = get_test_vsa_json();
+        json_data["verificationResult"] = json!("MAYBE");
+
+        assert!(serde_json::from_value::<SLSAVerificationSummaryV1Predicate>(json_data).is_err());
+    }
+
+    #[test]
+    fn deserialize_vsa_missing_policy() {
+        let mut json_data = get_test_vsa_json();
+        json_data.as_object_mut().unwrap().remove("policy");
+
+        assert!(serde_json::from_value::<SLSAVerificationSummaryV1Predicate>(json_data).is_err());
+    }
+}
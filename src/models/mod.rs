@@ -1,3 +1,4 @@
+pub mod dsse;
 mod helpers;
 pub mod intoto;
 pub mod sbom;
@@ -0,0 +1,81 @@
+//! DSSE signing of supply chain metadata documents.
+//!
+//! This allows generators using Spector as a library to sign the statements they build
+//! in the same process, rather than shelling out to a separate signing tool.
+
+use anyhow::Result;
+
+use crate::{
+    crypto::keys::PrivateKey,
+    models::{
+        dsse::envelope::{pae, Envelope, Signature, IN_TOTO_PAYLOAD_TYPE},
+        intoto::statement::InTotoStatementV1,
+    },
+};
+
+/// Wraps the payload in a DSSE envelope signed with the given private key.
+pub fn sign_envelope(
+    payload_type: &str,
+    payload: Vec<u8>,
+    key: &PrivateKey,
+    keyid: Option<String>,
+) -> Envelope {
+    let sig = key.sign(&pae(payload_type, &payload));
+    Envelope {
+        payload_type: payload_type.to_string(),
+        payload,
+        signatures: vec![Signature { keyid, sig }],
+    }
+}
+
+/// Serializes an In-Toto v1 statement and wraps it in a DSSE envelope signed with the given
+/// private key.
+pub fn sign_statement(
+    statement: &InTotoStatementV1,
+    key: &PrivateKey,
+    keyid: Option<String>,
+) -> Result<Envelope> {
+    let payload = serde_json::to_vec(statement)?;
+    Ok(sign_envelope(IN_TOTO_PAYLOAD_TYPE, payload, key, keyid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::dsse::{verify_envelope, TrustedKey};
+
+    const STATEMENT: &str = include_str!("../../tests/fixtures/slsa_provenance_v1.json");
+    const ED25519_PRIVATE_KEY: &str = include_str!("../../tests/fixtures/keys/ed25519.pem");
+    const ECDSA_P256_PRIVATE_KEY: &str = include_str!("../../tests/fixtures/keys/ecdsa_p256.pem");
+
+    #[test]
+    fn sign_statement_and_verify() {
+        let statement: InTotoStatementV1 = serde_json::from_str(STATEMENT).unwrap();
+
+        for pem in [ED25519_PRIVATE_KEY, ECDSA_P256_PRIVATE_KEY] {
+            let key = PrivateKey::from_pem(pem).unwrap();
+            let envelope = sign_statement(&statement, &key, Some("test-key".to_string())).unwrap();
+            assert_eq!(envelope.payload_type, IN_TOTO_PAYLOAD_TYPE);
+            assert_eq!(envelope.signatures[0].keyid.as_deref(), Some("test-key"));
+
+            let trusted = TrustedKey {
+                name: "test".to_string(),
+                key: key.public_key(),
+            };
+            let verified = verify_envelope(&envelope, &[trusted], 1).unwrap();
+            assert_eq!(verified.statement.subject, statement.subject);
+        }
+    }
+
+    #[test]
+    fn sign_envelope_arbitrary_payload() {
+        let key = PrivateKey::from_pem(ED25519_PRIVATE_KEY).unwrap();
+        let envelope = sign_envelope("text/plain", b"hello".to_vec(), &key, None);
+
+        assert_eq!(envelope.signatures[0].keyid, None);
+        assert!(key
+            .public_key()
+            .verify(&envelope.pae(), &envelope.signatures[0].sig)
+            .is_ok());
+    }
+}
@@ -0,0 +1,37 @@
+//! Helpers shared by unit tests.
+
+use std::path::{Path, PathBuf};
+
+/// A temporary directory unique to a test, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates an empty directory named after the test and the process ID, so that neither
+    /// tests nor concurrent test runs share files.
+    pub fn new(test: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("spector_{}_{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    /// Writes a file in the directory, returning its path.
+    pub fn write(&self, name: &str, contents: &[u8]) -> PathBuf {
+        let path = self.0.join(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,158 @@
+//! Validating many documents concurrently.
+//!
+//! Inputs are read and validated on a pool of threads, one validator being shared by all of
+//! them so that its compiled schemas are reused, and the results are returned in the order
+//! of the inputs whatever order they complete in.
+
+use rayon::prelude::*;
+use serde_json::Value;
+
+use super::{
+    report::{Diagnostic, ValidationReport},
+    Validator,
+};
+use crate::input::Input;
+
+/// The result of validating one input of a batch.
+#[derive(Debug)]
+pub struct BatchResult<T> {
+    pub input: Input,
+    /// The contents of the input if sources were kept and it could be read.
+    pub source: Option<String>,
+    /// The output of the validator, or the report of the problems found. Inputs that can't
+    /// be read or aren't JSON are reported with a `read` or `syntax` diagnostic.
+    pub result: Result<T, ValidationReport>,
+}
+
+/// Reads and validates inputs concurrently with `jobs` threads, or one per core if `jobs`
+/// is 0, returning a result per input in the order of the inputs.
+///
+/// The contents of the inputs are only kept in the results with `keep_sources`, e.g. to
+/// locate diagnostics in SARIF output, so that large batches aren't held in memory.
+pub fn validate_many<V>(
+    validator: &V,
+    inputs: &[Input],
+    jobs: usize,
+    keep_sources: bool,
+) -> anyhow::Result<Vec<BatchResult<V::Output>>>
+where
+    V: Validator + Sync,
+    V::Output: Send,
+{
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+    Ok(pool.install(|| {
+        inputs
+            .par_iter()
+            .map(|input| validate_input(validator, input, keep_sources))
+            .collect()
+    }))
+}
+
+// Reads and validates a single input.
+fn validate_input<V: Validator>(
+    validator: &V,
+    input: &Input,
+    keep_source: bool,
+) -> BatchResult<V::Output> {
+    let failure = |keyword: &str, message: String| {
+        let mut report = ValidationReport::new("JSON value");
+        report
+            .diagnostics
+            .push(Diagnostic::error("", keyword, message));
+        Err(report)
+    };
+
+    let source = match input.read_to_string() {
+        Ok(source) => source,
+        Err(err) => {
+            return BatchResult {
+                input: input.clone(),
+                source: None,
+                result: failure("read", format!("{:#}", err)),
+            }
+        }
+    };
+    let result = match serde_json::from_str::<Value>(&source) {
+        Ok(value) => validator.validate(&value),
+        Err(err) => failure("syntax", err.to_string()),
+    };
+    BatchResult {
+        input: input.clone(),
+        source: keep_source.then_some(source),
+        result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::intoto::statement::InTotoStatementV1, validate::pipeline::validate_value};
+    use std::path::PathBuf;
+
+    // Batches read their inputs themselves, so fixtures are given by path rather than
+    // included like in other tests.
+    fn fixture(name: &str) -> Input {
+        Input::File(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures")
+                .join(name),
+        )
+    }
+
+    #[test]
+    fn validate_many_in_order() {
+        let names = [
+            "slsa_provenance_v1.json",
+            "slsa_provenance_v1_multiple_errors.json",
+            "missing.json",
+            "in_toto_v1.rs",
+            "slsa_vsa_v1.json",
+        ];
+        let inputs = names
+            .iter()
+            .cycle()
+            .take(names.len() * 4)
+            .map(|name| fixture(name))
+            .collect::<Vec<_>>();
+
+        let results =
+            validate_many(&validate_value::<InTotoStatementV1>, &inputs, 4, true).unwrap();
+        assert_eq!(results.len(), inputs.len());
+        for (result, input) in results.iter().zip(&inputs) {
+            assert_eq!(&result.input, input);
+        }
+
+        let keywords = results[..names.len()]
+            .iter()
+            .map(|r| match &r.result {
+                Ok(_) => "valid".to_string(),
+                Err(report) => report.diagnostics[0].keyword.clone(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(keywords[0], "valid");
+        assert_ne!(keywords[1], "valid");
+        assert_eq!(keywords[2], "read");
+        assert_eq!(keywords[3], "syntax");
+        assert_eq!(keywords[4], "valid");
+        assert!(results[0].source.is_some());
+        assert!(results[2].source.is_none());
+    }
+
+    #[test]
+    fn validate_many_with_closure() {
+        let inputs = vec![fixture("slsa_provenance_v1.json")];
+        let validator = |value: &Value| -> Result<String, ValidationReport> {
+            Ok(value["predicateType"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string())
+        };
+
+        let results = validate_many(&validator, &inputs, 0, false).unwrap();
+        assert!(results[0].source.is_none());
+        assert_eq!(
+            results[0].result.as_ref().unwrap(),
+            "https://slsa.dev/provenance/v1"
+        );
+    }
+}
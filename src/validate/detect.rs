@@ -0,0 +1,277 @@
+//! Detecting the kind of a document from its contents.
+//!
+//! Documents are recognized by the fields identifying their format: `spdxVersion` for SPDX,
+//! `_type` for In-Toto statements, `payloadType` for DSSE envelopes and `bomFormat` for
+//! CycloneDX, so that they can be validated without knowing their kind up front.
+
+use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
+use serde_json::Value;
+
+use super::{
+    pipeline::{validate_statement, validate_value},
+    report::{Diagnostic, ValidationReport},
+    spdx::validate_spdx23,
+};
+use crate::models::{
+    dsse::envelope::{Envelope, IN_TOTO_PAYLOAD_TYPE},
+    intoto::statement::{STATEMENT_TYPE_V01, STATEMENT_TYPE_V1},
+    sbom::spdx22::Spdx22Document,
+};
+
+/// A kind of document recognized by `detect_document_kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentKind {
+    Spdx22,
+    Spdx23,
+    InTotoV1,
+    /// A statement with the legacy `https://in-toto.io/Statement/v0.1` `_type`, validated
+    /// as an In-Toto v1 statement with a warning.
+    InTotoV01,
+    Dsse,
+    /// A CycloneDX SBOM, which can be detected but not validated yet.
+    CycloneDx,
+}
+
+impl std::fmt::Display for DocumentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DocumentKind::Spdx22 => "SPDX v2.2",
+            DocumentKind::Spdx23 => "SPDX v2.3",
+            DocumentKind::InTotoV1 => "In-Toto v1 statement",
+            DocumentKind::InTotoV01 => "In-Toto v0.1 statement",
+            DocumentKind::Dsse => "DSSE envelope",
+            DocumentKind::CycloneDx => "CycloneDX",
+        })
+    }
+}
+
+/// Detects the kind of a document from the fields identifying its format.
+///
+/// Returns an error if the document has none of them, or an unsupported version.
+pub fn detect_document_kind(value: &Value) -> Result<DocumentKind> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| anyhow!("Unknown document kind: expected a JSON object"))?;
+    let field = |name: &str| object.get(name).map(|v| v.as_str().unwrap_or_default());
+
+    if let Some(version) = field("spdxVersion") {
+        return match version {
+            "SPDX-2.2" => Ok(DocumentKind::Spdx22),
+            "SPDX-2.3" => Ok(DocumentKind::Spdx23),
+            other => Err(anyhow!(
+                "Unsupported spdxVersion: {:?}, expected \"SPDX-2.2\" or \"SPDX-2.3\"",
+                other
+            )),
+        };
+    }
+    if let Some(statement_type) = field("_type") {
+        return match statement_type {
+            STATEMENT_TYPE_V1 => Ok(DocumentKind::InTotoV1),
+            STATEMENT_TYPE_V01 => Ok(DocumentKind::InTotoV01),
+            other => Err(anyhow!(
+                "Unsupported _type: {:?}, expected {:?}",
+                other,
+                STATEMENT_TYPE_V1
+            )),
+        };
+    }
+    if field("payloadType").is_some() && object.contains_key("signatures") {
+        return Ok(DocumentKind::Dsse);
+    }
+    if field("bomFormat") == Some("CycloneDX") {
+        return Ok(DocumentKind::CycloneDx);
+    }
+    Err(anyhow!(
+        "Unknown document kind: expected one of spdxVersion, _type, payloadType or bomFormat"
+    ))
+}
+
+/// Detects the kind of a document and validates it against the matching model.
+///
+/// Returns the detected kind, if any, with the report of the document. Documents whose kind
+/// can't be detected or validated are reported with a `detect` error.
+pub fn validate_detected(value: &Value) -> (Option<DocumentKind>, ValidationReport) {
+    let kind = match detect_document_kind(value) {
+        Ok(kind) => kind,
+        Err(err) => return (None, detect_error("JSON value", err.to_string())),
+    };
+    let report = match kind {
+        DocumentKind::Spdx22 => report_of(validate_value::<Spdx22Document>(value)),
+        DocumentKind::Spdx23 => match validate_spdx23(value) {
+            Ok((_, report)) | Err(report) => report,
+        },
+        DocumentKind::InTotoV1 | DocumentKind::InTotoV01 => {
+            match validate_statement(value, kind == DocumentKind::InTotoV01) {
+                Ok((_, report)) | Err(report) => report,
+            }
+        }
+        DocumentKind::Dsse => validate_envelope(value),
+        DocumentKind::CycloneDx => detect_error(
+            "CycloneDX",
+            "CycloneDX documents are not supported yet".to_string(),
+        ),
+    };
+    (Some(kind), report)
+}
+
+// Validates a DSSE envelope, and the In-Toto statement in its payload. Problems in the
+// payload are reported at `/payload`, as their paths within the decoded statement don't
+// resolve in the envelope, and the message names the path within the statement.
+fn validate_envelope(value: &Value) -> ValidationReport {
+    let envelope = match validate_value::<Envelope>(value) {
+        Ok(envelope) => envelope,
+        Err(report) => return report,
+    };
+    let mut report = ValidationReport::new(Envelope::schema_name());
+    if envelope.payload_type != IN_TOTO_PAYLOAD_TYPE {
+        report.diagnostics.push(Diagnostic {
+            expected: Some(IN_TOTO_PAYLOAD_TYPE.to_string()),
+            value: Some(Value::String(envelope.payload_type.clone())),
+            ..Diagnostic::error(
+                "/payloadType",
+                "const",
+                format!(
+                    "Unexpected payloadType: {:?}, expected {:?}",
+                    envelope.payload_type, IN_TOTO_PAYLOAD_TYPE
+                ),
+            )
+        });
+        return report;
+    }
+
+    let statement = match serde_json::from_slice::<Value>(&envelope.payload) {
+        Ok(statement) => statement,
+        Err(err) => {
+            report.diagnostics.push(Diagnostic::error(
+                "/payload",
+                "syntax",
+                format!("Failed to parse payload: {}", err),
+            ));
+            return report;
+        }
+    };
+    let statement_report = match validate_statement(&statement, false) {
+        Ok((_, report)) | Err(report) => report,
+    };
+    report.diagnostics.extend(
+        statement_report
+            .diagnostics
+            .into_iter()
+            .map(|d| Diagnostic {
+                message: match d.path.as_str() {
+                    "" => format!("{} (in the payload statement)", d.message),
+                    path => format!("{} (in the payload statement at {})", d.message, path),
+                },
+                path: "/payload".to_string(),
+                ..d
+            }),
+    );
+    report
+}
+
+// Returns the report of a validated model, which is empty if the model is valid.
+fn report_of<T: JsonSchema>(result: Result<T, ValidationReport>) -> ValidationReport {
+    result.map_or_else(|report| report, |_| ValidationReport::new(T::schema_name()))
+}
+
+fn detect_error(document_kind: &str, message: String) -> ValidationReport {
+    let mut report = ValidationReport::new(document_kind);
+    report
+        .diagnostics
+        .push(Diagnostic::error("", "detect", message));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const STATEMENT: &str = include_str!("../../tests/fixtures/slsa_provenance_v1.json");
+    const LEGACY_STATEMENT: &str =
+        include_str!("../../tests/fixtures/slsa_provenance_v1_legacy_type.json");
+    const ENVELOPE: &str = include_str!("../../tests/fixtures/dsse_slsa_provenance_v1.json");
+
+    fn fixture(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn detect_kinds() {
+        let cases = [
+            (json!({"spdxVersion": "SPDX-2.2"}), DocumentKind::Spdx22),
+            (json!({"spdxVersion": "SPDX-2.3"}), DocumentKind::Spdx23),
+            (fixture(STATEMENT), DocumentKind::InTotoV1),
+            (fixture(LEGACY_STATEMENT), DocumentKind::InTotoV01),
+            (fixture(ENVELOPE), DocumentKind::Dsse),
+            (
+                json!({"bomFormat": "CycloneDX", "specVersion": "1.5"}),
+                DocumentKind::CycloneDx,
+            ),
+        ];
+        for (value, kind) in cases {
+            assert_eq!(detect_document_kind(&value).unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn detect_unknown_kinds() {
+        for value in [
+            json!([]),
+            json!({"name": "unknown"}),
+            json!({"payloadType": "application/vnd.in-toto+json"}),
+        ] {
+            let err = detect_document_kind(&value).unwrap_err();
+            assert!(err.to_string().starts_with("Unknown document kind"));
+        }
+        let err = detect_document_kind(&json!({"spdxVersion": "SPDX-2.1"})).unwrap_err();
+        assert!(err.to_string().starts_with("Unsupported spdxVersion"));
+    }
+
+    #[test]
+    fn validate_detected_documents() {
+        let (kind, report) = validate_detected(&fixture(STATEMENT));
+        assert_eq!(kind, Some(DocumentKind::InTotoV1));
+        assert!(report.diagnostics.is_empty());
+
+        let (kind, report) = validate_detected(&fixture(LEGACY_STATEMENT));
+        assert_eq!(kind, Some(DocumentKind::InTotoV01));
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().count(), 1);
+
+        let (kind, report) = validate_detected(&fixture(ENVELOPE));
+        assert_eq!(kind, Some(DocumentKind::Dsse));
+        assert!(report.is_valid());
+
+        let (kind, report) = validate_detected(&json!({"bomFormat": "CycloneDX"}));
+        assert_eq!(kind, Some(DocumentKind::CycloneDx));
+        assert_eq!(report.diagnostics[0].keyword, "detect");
+    }
+
+    #[test]
+    fn validate_detected_envelope_payload() {
+        let mut statement = fixture(STATEMENT);
+        statement["predicate"]["runDetails"]["metadata"]["startedOn"] = json!(42);
+        let envelope = json!({
+            "payloadType": IN_TOTO_PAYLOAD_TYPE,
+            "payload": base64::Engine::encode(
+                &base64::engine::general_purpose::STANDARD,
+                statement.to_string(),
+            ),
+            "signatures": [],
+        });
+
+        let (_, report) = validate_detected(&envelope);
+        assert_eq!(report.document_kind, "Envelope");
+        assert_eq!(report.diagnostics[0].path, "/payload");
+        assert!(report.diagnostics[0].to_string().ends_with(
+            "(in the payload statement at /predicate/runDetails/metadata/startedOn) at /payload"
+        ));
+
+        let mut envelope = envelope;
+        envelope["payloadType"] = json!("application/json");
+        let (_, report) = validate_detected(&envelope);
+        assert_eq!(report.diagnostics[0].path, "/payloadType");
+    }
+}
@@ -5,36 +5,94 @@
 //! Serde will short-circuit on the first error it encounters. Thi means that if there are multiple
 //! the user will have to correct an error in their doc and repeat until Spector reports no more errors.
 
-use anyhow::{anyhow, Result};
+pub mod batch;
+pub mod detect;
+pub mod pipeline;
+pub mod report;
+pub mod sarif;
+pub mod spdx;
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
 use jsonschema::JSONSchema;
 use serde::de::DeserializeOwned;
-use serde_json::{from_value, Value};
+use serde_json::Value;
+
+use report::{Diagnostic, ValidationReport};
 
 /// A trait for implementing validation logic on JSON values.
 pub trait Validator {
     type Output;
 
     /// Validates the given JSON value and assuming no errors returns the deserialized output.
-    fn validate(&self, value: &Value) -> Result<Self::Output>;
+    /// Otherwise returns a report of the problems found.
+    fn validate(&self, value: &Value) -> Result<Self::Output, ValidationReport>;
+}
+
+/// Functions and closures validating a JSON value are validators too, e.g.
+/// `pipeline::validate_value::<T>`.
+impl<T, F> Validator for F
+where
+    F: Fn(&Value) -> Result<T, ValidationReport>,
+{
+    type Output = T;
+
+    fn validate(&self, value: &Value) -> Result<Self::Output, ValidationReport> {
+        self(value)
+    }
 }
 
 /// A JSON Schema-based validator for JSON values.
 ///
 /// The `JSONSchemaValidator` struct uses a JSON Schema to validate a JSON value and
-/// then deserializes if it is valid into the specified output type.
+/// then deserializes if it is valid into the specified output type. The schema is compiled
+/// once when the validator is created, and the validator is cheap to clone and can be shared
+/// across threads.
 pub struct JSONSchemaValidator<T: DeserializeOwned> {
-    schema: Value,
+    schema: Arc<JSONSchema>,
+    document_kind: String,
 
     // TODO(mlieberman85): this using phantomdata seems like an easy way to tell it return a deserialized values
-    // but I should probably look into if I can make this simpler.
-    _phantom: std::marker::PhantomData<T>,
+    // but I should probably look into if I can make this simpler. `fn() -> T` keeps the
+    // validator `Send` and `Sync` whatever `T` is, as it never holds a `T`.
+    _phantom: std::marker::PhantomData<fn() -> T>,
 }
 
 impl<T: DeserializeOwned> JSONSchemaValidator<T> {
-    /// Creates a new JSONSchemaValidator with the given JSON Schema.
-    pub fn new(schema: &Value) -> Self {
+    /// Creates a new JSONSchemaValidator with the given JSON Schema, failing if the schema
+    /// doesn't compile.
+    pub fn new(schema: &Value) -> anyhow::Result<Self> {
+        let compiled =
+            JSONSchema::compile(schema).map_err(|e| anyhow!("Failed to compile schema: {}", e))?;
+        Ok(Self {
+            schema: Arc::new(compiled),
+            // The document kind is the title of the schema, if any.
+            document_kind: schema
+                .get("title")
+                .and_then(Value::as_str)
+                .unwrap_or("JSON value")
+                .to_string(),
+            _phantom: std::marker::PhantomData,
+        })
+    }
+
+    /// Returns the compiled schema, e.g. to validate values without deserializing them.
+    pub fn schema(&self) -> &JSONSchema {
+        &self.schema
+    }
+
+    /// Returns the kind of document validated, the title of the schema if any.
+    pub fn document_kind(&self) -> &str {
+        &self.document_kind
+    }
+}
+
+impl<T: DeserializeOwned> Clone for JSONSchemaValidator<T> {
+    fn clone(&self) -> Self {
         Self {
-            schema: schema.clone(),
+            schema: Arc::clone(&self.schema),
+            document_kind: self.document_kind.clone(),
             _phantom: std::marker::PhantomData,
         }
     }
@@ -43,32 +101,23 @@ impl<T: DeserializeOwned> JSONSchemaValidator<T> {
 impl<T: DeserializeOwned> Validator for JSONSchemaValidator<T> {
     type Output = T;
 
-    fn validate(&self, value: &Value) -> Result<Self::Output> {
-        let schema = JSONSchema::compile(&self.schema)
-            .map_err(|e| anyhow!("Failed to compile schema: {}", e))?;
-
-        let validate = schema.validate(value);
-
-        match validate {
-            Ok(_) => {
-                let deserialized_value = from_value(value.clone())
-                    .map_err(|e| anyhow!("Failed to deserialize value: {}", e))?;
-                Ok(deserialized_value)
-            }
-            Err(e) => {
-                let error_messages = e
-                    .map(|e| {
-                        format!(
-                            "{}\npath: {}",
-                            serde_json::to_string_pretty(&e.instance).unwrap_or(e.to_string()),
-                            e.instance_path
-                        )
-                    })
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                Err(anyhow!("Failed to validate JSON value: {}", error_messages))
-            }
+    fn validate(&self, value: &Value) -> Result<Self::Output, ValidationReport> {
+        let mut report = ValidationReport::new(self.document_kind.as_str());
+        if let Err(errors) = self.schema.validate(value) {
+            report.diagnostics = errors
+                .map(|e| Diagnostic {
+                    expected: pipeline::expected(&e.kind),
+                    value: Some(e.instance.clone().into_owned()),
+                    ..Diagnostic::error(
+                        e.instance_path.to_string(),
+                        e.schema_path.clone().into_vec().pop().unwrap_or_default(),
+                        e.to_string(),
+                    )
+                })
+                .collect();
+            return Err(report);
         }
+        deserialize(value, report)
     }
 }
 
@@ -79,13 +128,24 @@ pub struct GenericValidator<T: DeserializeOwned> {
 impl<T: DeserializeOwned> Validator for GenericValidator<T> {
     type Output = T;
 
-    fn validate(&self, value: &Value) -> Result<Self::Output> {
-        let deserialized_value = from_value(value.clone())
-            .map_err(|e| anyhow!("Failed to deserialize value into type: {}", e))?;
-        Ok(deserialized_value)
+    fn validate(&self, value: &Value) -> Result<Self::Output, ValidationReport> {
+        deserialize(value, ValidationReport::new(std::any::type_name::<T>()))
     }
 }
 
+// Deserializes a value, adding the located error to the report on failure.
+fn deserialize<T: DeserializeOwned>(
+    value: &Value,
+    mut report: ValidationReport,
+) -> Result<T, ValidationReport> {
+    serde_path_to_error::deserialize(value).map_err(|err| {
+        report
+            .diagnostics
+            .push(pipeline::serde_error(&err, value, ""));
+        report
+    })
+}
+
 impl<T: DeserializeOwned> GenericValidator<T> {
     pub fn new() -> Self {
         Self {
@@ -94,6 +154,12 @@ impl<T: DeserializeOwned> GenericValidator<T> {
     }
 }
 
+impl<T: DeserializeOwned> Default for GenericValidator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
@@ -121,7 +187,7 @@ mod tests {
     #[test]
     fn test_jsonschema_valid_person() {
         let schema = person_schema();
-        let validator = JSONSchemaValidator::<Person>::new(&schema);
+        let validator = JSONSchemaValidator::<Person>::new(&schema).unwrap();
 
         let valid_value = json!({
             "name": "John Doe",
@@ -141,7 +207,7 @@ mod tests {
     #[test]
     fn test_jsonschema_invalid_person() {
         let schema = person_schema();
-        let validator = JSONSchemaValidator::<Person>::new(&schema);
+        let validator = JSONSchemaValidator::<Person>::new(&schema).unwrap();
 
         let invalid_value = json!({
             "name": 123,
@@ -151,6 +217,60 @@ mod tests {
         assert!(validator.validate(&invalid_value).is_err());
     }
 
+    #[test]
+    fn test_jsonschema_invalid_schema() {
+        let result = JSONSchemaValidator::<Person>::new(&json!({"type": 12}));
+        assert!(result
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("Failed to compile schema"));
+    }
+
+    #[test]
+    fn test_jsonschema_validator_shared_across_threads() {
+        let validator = JSONSchemaValidator::<Person>::new(&person_schema()).unwrap();
+
+        std::thread::scope(|scope| {
+            for age in 0..4 {
+                let validator = &validator;
+                scope.spawn(move || {
+                    let person = validator
+                        .validate(&json!({"name": "John Doe", "age": age}))
+                        .unwrap();
+                    assert_eq!(person.age, age);
+                    assert!(validator.validate(&json!({"age": age})).is_err());
+                });
+            }
+        });
+
+        let clone = validator.clone();
+        std::thread::spawn(move || clone.validate(&json!({"name": "Jane Doe", "age": 30})))
+            .join()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_jsonschema_invalid_person_report() {
+        let mut schema = person_schema();
+        schema["title"] = json!("Person");
+        let validator = JSONSchemaValidator::<Person>::new(&schema).unwrap();
+
+        let report = validator
+            .validate(&json!({"name": "John Doe", "age": "thirty"}))
+            .unwrap_err();
+        assert_eq!(report.document_kind, "Person");
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].path, "/age");
+        assert_eq!(report.diagnostics[0].keyword, "type");
+        assert_eq!(report.diagnostics[0].expected.as_deref(), Some("integer"));
+        assert_eq!(
+            report.to_string(),
+            "Failed to validate JSON value: \"thirty\"\npath: /age"
+        );
+    }
+
     #[test]
     fn test_generic_person_validation() {
         let validator = GenericValidator::<Person>::new();
@@ -160,7 +280,7 @@ mod tests {
         });
         let expected = Person {
             name: String::from("John Doe"),
-            age: 30
+            age: 30,
         };
         let result = validator.validate(&json_value).unwrap();
         assert_eq!(result, expected);
@@ -0,0 +1,448 @@
+//! A validation pipeline collecting every problem in a document.
+//!
+//! Deserialization stops at the first error, so a document is additionally validated
+//! against the JSON Schema generated from its model with `schemars`, which reports all
+//! problems at once. Deserialization errors are then located with `serde_path_to_error`, so
+//! checks the schema can't express, e.g. digest lengths, are reported with their location
+//! too. Every problem carries the JSON Pointer of the offending value.
+
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use jsonschema::{
+    error::{TypeKind, ValidationErrorKind},
+    JSONSchema,
+};
+use schemars::{schema::RootSchema, schema_for, JsonSchema};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use serde_path_to_error::{Segment, Track};
+
+use super::report::{Diagnostic, ValidationReport};
+use crate::models::intoto::{
+    registry,
+    statement::{InTotoStatementV1, Subject},
+};
+
+/// Validates a JSON value against the schema of `T` and deserializes it.
+///
+/// Returns a report of every problem found if the value doesn't match the schema or can't
+/// be deserialized.
+pub fn validate_value<T>(value: &Value) -> Result<T, ValidationReport>
+where
+    T: DeserializeOwned + JsonSchema + 'static,
+{
+    let mut report = ValidationReport::new(T::schema_name());
+    report.diagnostics = type_errors::<T>(value);
+    match serde_path_to_error::deserialize::<_, T>(value) {
+        Ok(deserialized) if report.is_valid() => Ok(deserialized),
+        Ok(_) => Err(report),
+        Err(err) => {
+            merge_serde_error(&mut report.diagnostics, serde_error(&err, value, ""));
+            Err(report)
+        }
+    }
+}
+
+/// Validates a JSON value as an In-Toto v1 statement and deserializes it.
+///
+/// The predicate is additionally validated against the schema of its registered predicate
+/// type, if any. With `lenient`, the legacy `https://in-toto.io/Statement/v0.1` `_type` is
+/// accepted with a warning, see `InTotoStatementV1::from_value_lenient`. The report
+/// returned alongside a valid statement holds such warnings.
+pub fn validate_statement(
+    value: &Value,
+    lenient: bool,
+) -> Result<(InTotoStatementV1, ValidationReport), ValidationReport> {
+    let mut report = ValidationReport::new(InTotoStatementV1::schema_name());
+    let mut errors = type_errors::<InTotoStatementV1>(value);
+
+    // Subjects and predicates are deserialized separately to locate their errors, which the
+    // statement deserializer reports without a location.
+    let mut located = Vec::new();
+    for (index, subject) in value
+        .get("subject")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        if let Err(err) = serde_path_to_error::deserialize::<_, Subject>(subject) {
+            let mut error = serde_error(&err, subject, &format!("/subject/{}", index));
+            // Match the message of the statement deserializer.
+            error.message = format!("subject[{}]: {}", index, error.message);
+            located.push(format!("subject[{}]: {}", index, err.inner()));
+            merge_serde_error(&mut errors, error);
+        }
+    }
+    // Like `deserialize_predicate`, only the predicate type URI selects a registration, not
+    // its name or alias.
+    let predicate_type = value.get("predicateType").and_then(Value::as_str);
+    let registration = predicate_type
+        .and_then(registry::lookup)
+        .filter(|r| Some(r.predicate_type.as_str()) == predicate_type);
+    if let (Some(predicate), Some(registration)) = (value.get("predicate"), &registration) {
+        let schema = cached(
+            SchemaKey::Predicate(registration.predicate_type.clone()),
+            || registration.schema.clone(),
+        );
+        errors.extend(compiled_errors(schema, predicate, "/predicate"));
+        if let Err(err) = registration.deserialize_with_path(predicate) {
+            located.push(err.inner().to_string());
+            merge_serde_error(&mut errors, serde_error(&err, predicate, "/predicate"));
+        }
+    }
+
+    let result = if lenient {
+        InTotoStatementV1::from_value_lenient(value)
+    } else {
+        InTotoStatementV1::deserialize(value).map(|s| (s, vec![]))
+    };
+    match result {
+        Ok((statement, warnings)) if errors.is_empty() => {
+            report.diagnostics = warnings
+                .into_iter()
+                .map(|warning| Diagnostic::warning("/_type", "const", warning))
+                .collect();
+            Ok((statement, report))
+        }
+        Ok(_) => {
+            report.diagnostics = errors;
+            Err(report)
+        }
+        Err(err) => {
+            if !located.contains(&err.to_string()) {
+                // Errors of the statement structure itself can be located by tracking the
+                // path, unlike those raised once it has been deserialized.
+                let err = match serde_path_to_error::deserialize::<_, InTotoStatementV1>(value) {
+                    Err(tracked) if tracked.inner().to_string() == err.to_string() => tracked,
+                    _ => serde_path_to_error::Error::new(Track::new().path(), err),
+                };
+                merge_serde_error(&mut errors, serde_error(&err, value, ""));
+            }
+            report.diagnostics = errors;
+            Err(report)
+        }
+    }
+}
+
+/// Validates a JSON value against a schema, returning every problem found.
+///
+/// The paths of the problems are prefixed with `prefix`, a JSON Pointer to the value
+/// within the document. The schema is compiled on each call, models validated with
+/// `validate_value` and registered predicates reuse their compiled schemas instead.
+pub fn schema_errors(schema: &RootSchema, value: &Value, prefix: &str) -> Vec<Diagnostic> {
+    compiled_errors(compile(schema), value, prefix)
+}
+
+// Validates a JSON value against the schema of `T`, compiled once per type.
+fn type_errors<T: JsonSchema + 'static>(value: &Value) -> Vec<Diagnostic> {
+    let schema = cached(SchemaKey::Type(TypeId::of::<T>()), || schema_for!(T));
+    compiled_errors(schema, value, "")
+}
+
+// Validates a JSON value against a compiled schema, or reports why it didn't compile.
+fn compiled_errors(
+    schema: Result<Arc<JSONSchema>, String>,
+    value: &Value,
+    prefix: &str,
+) -> Vec<Diagnostic> {
+    let schema = match schema {
+        Ok(schema) => schema,
+        Err(err) => {
+            return vec![Diagnostic::error(
+                prefix,
+                "schema",
+                format!("Failed to compile schema: {}", err),
+            )]
+        }
+    };
+
+    let errors = match schema.validate(value) {
+        Ok(()) => return Vec::new(),
+        Err(errors) => errors,
+    };
+    errors
+        .map(|error| {
+            let keyword = error
+                .schema_path
+                .clone()
+                .into_vec()
+                .pop()
+                .unwrap_or_default();
+            let expected = expected(&error.kind);
+            // The instance of a missing property error is the whole parent object.
+            let value = match error.kind {
+                ValidationErrorKind::Required { .. } => None,
+                _ => Some(error.instance.clone().into_owned()),
+            };
+            Diagnostic {
+                expected,
+                value,
+                ..Diagnostic::error(
+                    format!("{}{}", prefix, error.instance_path),
+                    keyword,
+                    error.to_string(),
+                )
+            }
+        })
+        .collect()
+}
+
+// The key of a compiled schema in the cache: the type of a model, or the predicate type
+// URI of a registered predicate, which can't be registered twice.
+#[derive(PartialEq, Eq, Hash)]
+enum SchemaKey {
+    Type(TypeId),
+    Predicate(String),
+}
+
+// Returns a compiled schema from the cache, compiling the schema returned by `schema` on
+// first use.
+fn cached(key: SchemaKey, schema: impl FnOnce() -> RootSchema) -> Result<Arc<JSONSchema>, String> {
+    static COMPILED: OnceLock<RwLock<HashMap<SchemaKey, Arc<JSONSchema>>>> = OnceLock::new();
+    let compiled = COMPILED.get_or_init(Default::default);
+
+    if let Some(schema) = compiled.read().ok().and_then(|c| c.get(&key).cloned()) {
+        return Ok(schema);
+    }
+    let schema = compile(&schema())?;
+    if let Ok(mut compiled) = compiled.write() {
+        compiled.insert(key, Arc::clone(&schema));
+    }
+    Ok(schema)
+}
+
+fn compile(schema: &RootSchema) -> Result<Arc<JSONSchema>, String> {
+    let value = serde_json::to_value(schema).map_err(|e| e.to_string())?;
+    let schema = JSONSchema::compile(&value).map_err(|e| e.to_string())?;
+    Ok(Arc::new(schema))
+}
+
+// Returns the expected type or values of a schema error, if known.
+pub(crate) fn expected(kind: &ValidationErrorKind) -> Option<String> {
+    match kind {
+        ValidationErrorKind::Type {
+            kind: TypeKind::Single(kind),
+        } => Some(kind.to_string()),
+        ValidationErrorKind::Type {
+            kind: TypeKind::Multiple(kinds),
+        } => Some(
+            kinds
+                .into_iter()
+                .map(|kind| kind.to_string())
+                .collect::<Vec<_>>()
+                .join(" or "),
+        ),
+        ValidationErrorKind::Enum { options } => Some(options.to_string()),
+        ValidationErrorKind::Constant { expected_value } => Some(expected_value.to_string()),
+        ValidationErrorKind::Format { format } => Some(format.to_string()),
+        _ => None,
+    }
+}
+
+// Converts a located deserialization error of `value` into a diagnostic.
+pub(crate) fn serde_error(
+    err: &serde_path_to_error::Error<serde_json::Error>,
+    value: &Value,
+    prefix: &str,
+) -> Diagnostic {
+    let pointer = err
+        .path()
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(key.replace('~', "~0").replace('/', "~1")),
+            Segment::Enum { .. } | Segment::Unknown => None,
+        })
+        .fold(String::new(), |pointer, segment| {
+            format!("{}/{}", pointer, segment)
+        });
+    let message = err.inner().to_string();
+    // serde_json appends the location in the input, which isn't meaningful for a value.
+    let message = match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message,
+    };
+
+    let keyword = if message.starts_with("missing field") {
+        "required"
+    } else if message.starts_with("invalid type") {
+        "type"
+    } else if message.starts_with("unknown variant") {
+        "enum"
+    } else if message.starts_with("unknown field") {
+        "additionalProperties"
+    } else {
+        "deserialize"
+    };
+    let expected = message
+        .split_once(", expected ")
+        .map(|(_, expected)| expected.to_string());
+
+    Diagnostic {
+        expected,
+        value: match keyword {
+            "required" => None,
+            _ => value.pointer(&pointer).cloned(),
+        },
+        ..Diagnostic::error(format!("{}{}", prefix, pointer), keyword, message)
+    }
+}
+
+// Adds a deserialization error, replacing any schema error it duplicates. The expected
+// type of the schema error is kept, as it names a JSON type rather than a Rust type.
+fn merge_serde_error(errors: &mut Vec<Diagnostic>, mut error: Diagnostic) {
+    if let Some(index) = errors
+        .iter()
+        .position(|e| e.path == error.path && e.keyword == error.keyword)
+    {
+        let duplicate = errors.remove(index);
+        error.expected = duplicate.expected.or(error.expected);
+    }
+    errors.push(error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    #[test]
+    fn validate_value_collects_all_errors() {
+        let report = validate_value::<Person>(&json!({"age": "thirty"})).unwrap_err();
+        assert_eq!(report.document_kind, "Person");
+        let errors = report.diagnostics;
+        assert_eq!(errors.len(), 2);
+
+        let type_error = errors.iter().find(|e| e.keyword == "type").unwrap();
+        assert_eq!(type_error.path, "/age");
+        assert_eq!(type_error.expected.as_deref(), Some("integer"));
+        assert_eq!(type_error.value, Some(json!("thirty")));
+
+        let required = errors.iter().find(|e| e.keyword == "required").unwrap();
+        assert_eq!(required.path, "");
+        assert_eq!(required.value, None);
+    }
+
+    #[test]
+    fn validate_value_valid() {
+        let person = validate_value::<Person>(&json!({"name": "John Doe", "age": 30})).unwrap();
+        assert_eq!(person.age, 30);
+    }
+
+    fn statement() -> Value {
+        serde_json::from_str(include_str!("../../tests/fixtures/slsa_provenance_v1.json")).unwrap()
+    }
+
+    #[test]
+    fn validate_statement_locates_predicate_errors() {
+        let mut value = statement();
+        let build_definition = value["predicate"]["buildDefinition"]
+            .as_object_mut()
+            .unwrap();
+        build_definition.remove("buildType");
+        build_definition.insert("resolvedDependencies".to_string(), json!("none"));
+        value["predicate"]["runDetails"]["metadata"]["startedOn"] = json!(42);
+
+        let errors = validate_statement(&value, false).unwrap_err().diagnostics;
+        let paths = errors.iter().map(|e| e.path.as_str()).collect::<Vec<_>>();
+        assert!(paths.contains(&"/predicate/buildDefinition"));
+        assert!(paths.contains(&"/predicate/buildDefinition/resolvedDependencies"));
+        assert!(paths.contains(&"/predicate/runDetails/metadata/startedOn"));
+
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn validate_statement_prefers_deserialization_errors() {
+        let mut value = statement();
+        value["predicate"]["buildDefinition"]
+            .as_object_mut()
+            .unwrap()
+            .remove("buildType");
+
+        // The deserialization error replaces the equivalent schema error.
+        let errors = validate_statement(&value, false).unwrap_err().diagnostics;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].keyword, "required");
+        assert_eq!(
+            errors[0].to_string(),
+            "missing field `buildType` at /predicate/buildDefinition"
+        );
+    }
+
+    #[test]
+    fn validate_statement_locates_deserialization_only_errors() {
+        let mut value = statement();
+        value["predicate"]["buildDefinition"]["resolvedDependencies"][0]["digest"]["gitCommit"] =
+            json!("c27d339");
+
+        let errors = validate_statement(&value, false).unwrap_err().diagnostics;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].keyword, "deserialize");
+        assert_eq!(
+            errors[0].path,
+            "/predicate/buildDefinition/resolvedDependencies/0/digest"
+        );
+        assert!(errors[0].message.starts_with("invalid gitCommit digest"));
+    }
+
+    #[test]
+    fn validate_statement_matches_predicate_type_uri() {
+        #[derive(Deserialize, JsonSchema)]
+        struct AliasedPredicate {
+            #[allow(dead_code)]
+            name: String,
+        }
+        registry::register::<AliasedPredicate>(
+            "https://example.com/pipeline-aliased/v1",
+            "PipelineAliasedV1",
+            "https://example.com/pipeline-alias/v1",
+        )
+        .unwrap();
+
+        let mut value = statement();
+        value["predicate"] = json!({});
+        value["predicateType"] = json!("https://example.com/pipeline-alias/v1");
+        assert!(validate_statement(&value, false).is_ok());
+
+        value["predicateType"] = json!("https://example.com/pipeline-aliased/v1");
+        let errors = validate_statement(&value, false).unwrap_err().diagnostics;
+        assert_eq!(errors[0].path, "/predicate");
+    }
+
+    #[test]
+    fn validate_statement_envelope_errors() {
+        let mut value = statement();
+        value["_type"] = json!("https://in-toto.io/Statement/v0.1");
+        value["subject"] = json!({});
+
+        let errors = validate_statement(&value, true).unwrap_err().diagnostics;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "/subject");
+        assert_eq!(errors[0].keyword, "type");
+
+        value["subject"] = json!([{"digest": {"sha256": "abc"}}]);
+        let errors = validate_statement(&value, true).unwrap_err().diagnostics;
+        let paths = errors.iter().map(|e| e.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["/subject/0", "/subject/0/digest"]);
+
+        value["subject"] = statement()["subject"].clone();
+        let (_, report) = validate_statement(&value, true).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().count(), 1);
+        assert_eq!(report.diagnostics[0].path, "/_type");
+        assert!(validate_statement(&value, false).is_err());
+    }
+}
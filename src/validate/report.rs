@@ -0,0 +1,199 @@
+//! Structured results of validating a document.
+//!
+//! A `ValidationReport` lists a `Diagnostic` per problem found, so that library users can
+//! inspect problems programmatically, and the CLI can render them as text or in machine
+//! readable formats.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// The severity of a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The document is invalid.
+    Error,
+    /// The document is valid, but likely not what was intended, e.g. it uses a legacy type.
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => f.write_str("error"),
+            Severity::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A single problem found while validating a document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    /// The JSON Pointer of the offending value, empty for the document itself.
+    pub path: String,
+    /// The JSON Schema keyword that failed, e.g. `required` or `type`, or `deserialize` for
+    /// checks only done when deserializing.
+    pub keyword: String,
+    pub message: String,
+    pub severity: Severity,
+    /// The expected type or values, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// The offending value at `path`, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+}
+
+impl Diagnostic {
+    /// Creates an error diagnostic.
+    pub fn error(
+        path: impl Into<String>,
+        keyword: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Diagnostic {
+            path: path.into(),
+            keyword: keyword.into(),
+            message: message.into(),
+            severity: Severity::Error,
+            expected: None,
+            value: None,
+        }
+    }
+
+    /// Creates a warning diagnostic.
+    pub fn warning(
+        path: impl Into<String>,
+        keyword: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(path, keyword, message)
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)?;
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of validating a document, with a diagnostic per problem found.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationReport {
+    /// The kind of document validated, e.g. `InTotoV1` or the title of a JSON Schema.
+    #[serde(rename = "documentKind")]
+    pub document_kind: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    /// Creates an empty report for a kind of document.
+    pub fn new(document_kind: impl Into<String>) -> Self {
+        ValidationReport {
+            document_kind: document_kind.into(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Returns true if no diagnostic is an error.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Returns the error diagnostics.
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+    }
+
+    /// Returns the warning diagnostics.
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+}
+
+impl std::fmt::Display for ValidationReport {
+    /// Formats the report listing the offending value, or message if there is none, of each
+    /// diagnostic followed by its path on the next line. Pretty-printed values span several
+    /// lines too.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let diagnostics = self
+            .diagnostics
+            .iter()
+            .map(|d| {
+                let value = d
+                    .value
+                    .as_ref()
+                    .and_then(|value| serde_json::to_string_pretty(value).ok())
+                    .unwrap_or_else(|| d.message.clone());
+                format!("{}\npath: {}", value, d.path)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "Failed to validate JSON value: {}", diagnostics)
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn report() -> ValidationReport {
+        let mut report = ValidationReport::new("Person");
+        report.diagnostics.push(Diagnostic {
+            expected: Some("integer".to_string()),
+            value: Some(json!("thirty")),
+            ..Diagnostic::error("/age", "type", "\"thirty\" is not of type \"integer\"")
+        });
+        report.diagnostics.push(Diagnostic::warning(
+            "",
+            "required",
+            "\"name\" is a required property",
+        ));
+        report
+    }
+
+    #[test]
+    fn report_severities() {
+        let report = report();
+        assert!(!report.is_valid());
+        assert_eq!(report.errors().count(), 1);
+        assert_eq!(report.warnings().count(), 1);
+        assert!(ValidationReport::new("Person").is_valid());
+    }
+
+    #[test]
+    fn display_report() {
+        assert_eq!(
+            report().to_string(),
+            "Failed to validate JSON value: \"thirty\"\npath: /age, \
+             \"name\" is a required property\npath: "
+        );
+        assert_eq!(
+            report().diagnostics[0].to_string(),
+            "\"thirty\" is not of type \"integer\" at /age"
+        );
+    }
+
+    #[test]
+    fn serialize_report() {
+        let value = serde_json::to_value(report()).unwrap();
+        assert_eq!(value["documentKind"], json!("Person"));
+        assert_eq!(value["diagnostics"][0]["severity"], json!("error"));
+        assert_eq!(value["diagnostics"][0]["expected"], json!("integer"));
+        assert!(value["diagnostics"][1].get("value").is_none());
+    }
+}
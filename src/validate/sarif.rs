@@ -0,0 +1,291 @@
+//! SARIF output of validation reports.
+//!
+//! Validation reports are converted into a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! log so that CI systems and code scanning UIs can ingest them. The JSON Pointer of each
+//! diagnostic is mapped to a region in the validated file, so the offending line is
+//! highlighted.
+
+use serde_json::{json, Value};
+
+use super::report::{Severity, ValidationReport};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// A region of a text file, with 1-based lines and columns counted in UTF-16 code units as
+/// SARIF expects by default. The end column is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// Returns the region of the value a JSON Pointer refers to in a JSON document.
+///
+/// Returns `None` if the source isn't valid JSON up to the value or the pointer doesn't
+/// refer to a value.
+pub fn locate(source: &str, pointer: &str) -> Option<Region> {
+    let bytes = source.as_bytes();
+    let mut pos = skip_whitespace(bytes, 0);
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        pos = match bytes.get(pos)? {
+            b'{' => find_member(source, pos, &token)?,
+            b'[' => find_element(bytes, pos, token.parse().ok()?)?,
+            _ => return None,
+        };
+    }
+    let end = skip_value(bytes, pos)?;
+    let (start_line, start_column) = line_column(source, pos);
+    let (end_line, end_column) = line_column(source, end);
+    Some(Region {
+        start_line,
+        start_column,
+        end_line,
+        end_column,
+    })
+}
+
+/// Builds a SARIF log with a result per diagnostic of the given reports.
+///
+/// Each report is given with the URI of the validated file, used as the artifact location
+/// of its results, and the file contents, used to locate the diagnostics.
+pub fn sarif_log<'a>(
+    reports: impl IntoIterator<Item = (&'a str, &'a str, &'a ValidationReport)>,
+) -> Value {
+    let mut rules: Vec<String> = Vec::new();
+    let mut results = Vec::new();
+    for (uri, source, report) in reports {
+        for diagnostic in &report.diagnostics {
+            if !rules.contains(&diagnostic.keyword) {
+                rules.push(diagnostic.keyword.clone());
+            }
+            let mut location = json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri },
+                },
+            });
+            if let Some(region) = locate(source, &diagnostic.path) {
+                location["physicalLocation"]["region"] = json!({
+                    "startLine": region.start_line,
+                    "startColumn": region.start_column,
+                    "endLine": region.end_line,
+                    "endColumn": region.end_column,
+                });
+            }
+            if !diagnostic.path.is_empty() {
+                location["logicalLocations"] = json!([{
+                    "fullyQualifiedName": diagnostic.path,
+                    "kind": "element",
+                }]);
+            }
+            results.push(json!({
+                "ruleId": diagnostic.keyword,
+                "level": match diagnostic.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                "message": { "text": diagnostic.to_string() },
+                "locations": [location],
+                "properties": { "documentKind": report.document_kind },
+            }));
+        }
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "spector",
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules
+                        .iter()
+                        .map(|rule| json!({ "id": rule }))
+                        .collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+// Returns the position of the value of the member named `key` of the object at `pos`.
+fn find_member(source: &str, pos: usize, key: &str) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut pos = skip_whitespace(bytes, pos + 1);
+    if bytes.get(pos)? == &b'}' {
+        return None;
+    }
+    loop {
+        let key_end = skip_string(bytes, pos)?;
+        let name = serde_json::from_str::<String>(&source[pos..key_end]).ok()?;
+        pos = skip_whitespace(bytes, key_end);
+        if bytes.get(pos)? != &b':' {
+            return None;
+        }
+        pos = skip_whitespace(bytes, pos + 1);
+        if name == key {
+            return Some(pos);
+        }
+        pos = skip_whitespace(bytes, skip_value(bytes, pos)?);
+        match bytes.get(pos)? {
+            b',' => pos = skip_whitespace(bytes, pos + 1),
+            _ => return None,
+        }
+    }
+}
+
+// Returns the position of the element at `index` of the array at `pos`.
+fn find_element(bytes: &[u8], pos: usize, index: usize) -> Option<usize> {
+    let mut pos = skip_whitespace(bytes, pos + 1);
+    if bytes.get(pos)? == &b']' {
+        return None;
+    }
+    for _ in 0..index {
+        pos = skip_whitespace(bytes, skip_value(bytes, pos)?);
+        match bytes.get(pos)? {
+            b',' => pos = skip_whitespace(bytes, pos + 1),
+            _ => return None,
+        }
+    }
+    Some(pos)
+}
+
+// Returns the position just past the value at `pos`.
+fn skip_value(bytes: &[u8], pos: usize) -> Option<usize> {
+    match bytes.get(pos)? {
+        b'"' => skip_string(bytes, pos),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut pos = pos;
+            loop {
+                match bytes.get(pos)? {
+                    b'"' => {
+                        pos = skip_string(bytes, pos)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(pos + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                pos += 1;
+            }
+        }
+        _ => {
+            let len = bytes[pos..]
+                .iter()
+                .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+                .unwrap_or(bytes.len() - pos);
+            Some(pos + len)
+        }
+    }
+}
+
+// Returns the position just past the string starting at `pos`.
+fn skip_string(bytes: &[u8], pos: usize) -> Option<usize> {
+    if bytes.get(pos)? != &b'"' {
+        return None;
+    }
+    let mut pos = pos + 1;
+    loop {
+        match bytes.get(pos)? {
+            b'\\' => pos += 2,
+            b'"' => return Some(pos + 1),
+            _ => pos += 1,
+        }
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], pos: usize) -> usize {
+    pos + bytes
+        .get(pos..)
+        .unwrap_or_default()
+        .iter()
+        .take_while(|b| b.is_ascii_whitespace())
+        .count()
+}
+
+// Converts a byte offset into a 1-based line and UTF-16 column.
+fn line_column(source: &str, pos: usize) -> (usize, usize) {
+    let before = &source[..pos];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].encode_utf16().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::report::Diagnostic;
+
+    const SOURCE: &str = r#"{
+  "name": "Jane \"J\" Doe",
+  "tags": ["a", {"x/y": [1, 2]}],
+  "age": "thirty",
+  "é": null
+}"#;
+
+    fn region(pointer: &str) -> Option<(usize, usize, usize, usize)> {
+        locate(SOURCE, pointer).map(|r| (r.start_line, r.start_column, r.end_line, r.end_column))
+    }
+
+    #[test]
+    fn locate_values() {
+        assert_eq!(region(""), Some((1, 1, 6, 2)));
+        assert_eq!(region("/name"), Some((2, 11, 2, 27)));
+        assert_eq!(region("/age"), Some((4, 10, 4, 18)));
+        assert_eq!(region("/tags/1/x~1y/1"), Some((3, 29, 3, 30)));
+        assert_eq!(region("/é"), Some((5, 8, 5, 12)));
+    }
+
+    #[test]
+    fn locate_missing_values() {
+        assert_eq!(region("/missing"), None);
+        assert_eq!(region("/tags/2"), None);
+        assert_eq!(region("/name/0"), None);
+        assert_eq!(locate("{\"a\": ", "/a"), None);
+    }
+
+    #[test]
+    fn sarif_results() {
+        let mut report = ValidationReport::new("Person");
+        report.diagnostics.push(Diagnostic::error(
+            "/age",
+            "type",
+            "\"thirty\" is not of type \"integer\"",
+        ));
+        report
+            .diagnostics
+            .push(Diagnostic::warning("/missing", "const", "legacy"));
+
+        let log = sarif_log([("person.json", SOURCE, &report)]);
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "type");
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "person.json");
+        assert_eq!(location["region"]["startLine"], 4);
+        assert_eq!(location["region"]["startColumn"], 10);
+
+        let result = &run["results"][1];
+        assert_eq!(result["level"], "warning");
+        assert!(result["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+    }
+}
@@ -0,0 +1,551 @@
+//! Semantic checks of SPDX documents.
+//!
+//! The JSON Schema of a document only checks its shape. These checks cover the rules of the
+//! SPDX specification it can't express: element identifiers must be unique and well formed,
+//! relationships must refer to defined elements, the document creation information must be
+//! valid, and license expressions must only use licenses of the SPDX License List or defined
+//! in the document.
+//!
+//! Only SPDX 2.3 documents are checked, as the SPDX 2.2 model lacks element identifiers.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDateTime;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+use url::Url;
+
+use super::{
+    pipeline::validate_value,
+    report::{Diagnostic, ValidationReport},
+};
+use crate::{
+    license::{
+        expression::{LicenseExpression, SimpleLicense},
+        list::{lookup_exception, lookup_license},
+    },
+    models::sbom::spdx23::Spdx23,
+};
+
+/// The `SPDXID` of the document itself.
+pub const DOCUMENT_SPDXID: &str = "SPDXRef-DOCUMENT";
+/// The only data license allowed for SPDX documents.
+pub const DATA_LICENSE: &str = "CC0-1.0";
+
+const SPDXID_PREFIX: &str = "SPDXRef-";
+const DOCUMENT_REF_PREFIX: &str = "DocumentRef-";
+// The format of timestamps, e.g. `2010-01-29T18:30:22Z`.
+const CREATED_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+/// Validates an SPDX 2.3 document against its JSON Schema, model and semantic checks.
+///
+/// Returns the document with the report of its warnings if it is valid. The semantic checks
+/// still run if the document only fails its JSON Schema, and are otherwise reported as
+/// skipped.
+pub fn validate_spdx23(value: &Value) -> Result<(Spdx23, ValidationReport), ValidationReport> {
+    let document = match validate_value::<Spdx23>(value) {
+        Ok(document) => document,
+        Err(mut report) => {
+            match Spdx23::deserialize(value) {
+                Ok(document) => report.diagnostics.extend(check_spdx23(&document)),
+                Err(_) => report.diagnostics.push(Diagnostic::warning(
+                    "",
+                    "spdx-skipped",
+                    "Semantic checks skipped, as the document doesn't match the SPDX 2.3 model"
+                        .to_string(),
+                )),
+            }
+            return Err(report);
+        }
+    };
+    let mut report = ValidationReport::new(Spdx23::schema_name());
+    report.diagnostics = check_spdx23(&document);
+    match report.is_valid() {
+        true => Ok((document, report)),
+        false => Err(report),
+    }
+}
+
+/// Returns a diagnostic per violation of the SPDX 2.3 specification in a document.
+///
+/// Deprecated license and exception identifiers are reported as warnings.
+pub fn check_spdx23(document: &Spdx23) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if document.data_license != DATA_LICENSE {
+        diagnostics.push(Diagnostic {
+            expected: Some(DATA_LICENSE.to_string()),
+            value: Some(Value::String(document.data_license.clone())),
+            ..Diagnostic::error(
+                "/dataLicense",
+                "spdx-data-license",
+                format!(
+                    "Invalid dataLicense {:?}, expected {:?}",
+                    document.data_license, DATA_LICENSE
+                ),
+            )
+        });
+    }
+
+    let created = &document.creation_info.created;
+    if NaiveDateTime::parse_from_str(created, CREATED_FORMAT).is_err() {
+        diagnostics.push(Diagnostic {
+            expected: Some("YYYY-MM-DDThh:mm:ssZ".to_string()),
+            value: Some(Value::String(created.clone())),
+            ..Diagnostic::error(
+                "/creationInfo/created",
+                "spdx-created",
+                format!(
+                    "Invalid created timestamp {:?}, expected YYYY-MM-DDThh:mm:ssZ",
+                    created
+                ),
+            )
+        });
+    }
+
+    let namespaces = document
+        .external_document_refs
+        .iter()
+        .map(|r| r.spdx_document.as_str())
+        .collect::<Vec<_>>();
+    if let Some(message) = check_namespace(&document.document_namespace, &namespaces) {
+        diagnostics.push(Diagnostic {
+            value: Some(Value::String(document.document_namespace.clone())),
+            ..Diagnostic::error("/documentNamespace", "spdx-namespace", message)
+        });
+    }
+
+    // The path of the SPDXID of each element.
+    let mut elements = HashMap::new();
+    let ids = std::iter::once(("/SPDXID".to_string(), &document.spdxid))
+        .chain(ids("packages", document.packages.iter().map(|p| &p.spdxid)))
+        .chain(ids("files", document.files.iter().map(|f| &f.spdxid)))
+        .chain(ids("snippets", document.snippets.iter().map(|s| &s.spdxid)));
+    for (path, id) in ids {
+        if let Some(message) = check_spdxid(id, path == "/SPDXID") {
+            diagnostics.push(Diagnostic {
+                value: Some(Value::String(id.clone())),
+                ..Diagnostic::error(&path, "spdx-id", message)
+            });
+        }
+        match elements.get(id.as_str()) {
+            Some(first) => diagnostics.push(Diagnostic::error(
+                &path,
+                "spdx-id",
+                format!("Duplicate SPDXID {:?} (first defined at {})", id, first),
+            )),
+            None => {
+                elements.insert(id.as_str(), path);
+            }
+        }
+    }
+
+    let document_refs = document
+        .external_document_refs
+        .iter()
+        .map(|r| r.external_document_id.as_str())
+        .collect::<HashSet<_>>();
+    for (i, relationship) in document.relationships.iter().enumerate() {
+        let references = [
+            ("spdxElementId", &relationship.spdx_element_id, false),
+            (
+                "relatedSpdxElement",
+                &relationship.related_spdx_element,
+                true,
+            ),
+        ];
+        for (field, id, related) in references {
+            // Only the related element can be unknown or absent.
+            if related && (id == "NOASSERTION" || id == "NONE") {
+                continue;
+            }
+            let defined = match id.split_once(':') {
+                Some((document_ref, _)) => {
+                    document_ref.starts_with(DOCUMENT_REF_PREFIX)
+                        && document_refs.contains(document_ref)
+                }
+                None => elements.contains_key(id.as_str()),
+            };
+            if !defined {
+                diagnostics.push(Diagnostic {
+                    value: Some(Value::String(id.clone())),
+                    ..Diagnostic::error(
+                        format!("/relationships/{}/{}", i, field),
+                        "spdx-reference",
+                        format!("Undefined SPDX element {:?}", id),
+                    )
+                });
+            }
+        }
+    }
+
+    let license_refs = document
+        .has_extracted_licensing_infos
+        .iter()
+        .map(|info| info.license_id.as_str())
+        .collect::<HashSet<_>>();
+    let expressions = document
+        .packages
+        .iter()
+        .enumerate()
+        .flat_map(|(i, p)| {
+            licenses(
+                format!("/packages/{}", i),
+                [
+                    ("licenseConcluded", &p.license_concluded),
+                    ("licenseDeclared", &p.license_declared),
+                ],
+                ("licenseInfoFromFiles", &p.license_info_from_files),
+            )
+        })
+        .chain(document.files.iter().enumerate().flat_map(|(i, f)| {
+            licenses(
+                format!("/files/{}", i),
+                [("licenseConcluded", &f.license_concluded)],
+                ("licenseInfoInFiles", &f.license_info_in_files),
+            )
+        }))
+        .chain(document.snippets.iter().enumerate().flat_map(|(i, s)| {
+            licenses(
+                format!("/snippets/{}", i),
+                [("licenseConcluded", &s.license_concluded)],
+                ("licenseInfoInSnippets", &s.license_info_in_snippets),
+            )
+        }));
+    for (path, expression) in expressions {
+        diagnostics.extend(check_license_expression(
+            &path,
+            expression,
+            &license_refs,
+            &document_refs,
+        ));
+    }
+
+    diagnostics
+}
+
+// Returns the paths and values of the license expressions of an element, given as single
+// expression fields and a list of licenses.
+fn licenses<'a, const N: usize>(
+    element: String,
+    fields: [(&'static str, &'a Option<String>); N],
+    (list, values): (&'static str, &'a [String]),
+) -> Vec<(String, &'a str)> {
+    fields
+        .into_iter()
+        .filter_map(|(field, value)| Some((format!("{}/{}", element, field), value.as_deref()?)))
+        .chain(
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (format!("{}/{}/{}", element, list, i), value.as_str())),
+        )
+        .collect()
+}
+
+// Checks that a license expression parses, and that its licenses and exceptions are in the
+// SPDX License List or defined by the document.
+fn check_license_expression(
+    path: &str,
+    expression: &str,
+    license_refs: &HashSet<&str>,
+    document_refs: &HashSet<&str>,
+) -> Vec<Diagnostic> {
+    let value = Some(Value::String(expression.to_string()));
+    let parsed = match LicenseExpression::parse(expression) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return vec![Diagnostic {
+                value,
+                ..Diagnostic::error(path, "spdx-license", err.to_string())
+            }]
+        }
+    };
+
+    let mut diagnostics = Vec::new();
+    let mut report = |diagnostic: Diagnostic| {
+        diagnostics.push(Diagnostic {
+            value: value.clone(),
+            ..diagnostic
+        })
+    };
+    for term in parsed.terms() {
+        match &term.license {
+            SimpleLicense::Id { id, .. } => match lookup_license(id) {
+                None => report(Diagnostic::error(
+                    path,
+                    "spdx-license",
+                    format!("Unknown license identifier {:?}", id),
+                )),
+                Some(listed) if listed.deprecated => report(Diagnostic::warning(
+                    path,
+                    "spdx-license",
+                    format!("Deprecated license identifier {:?}", listed.id),
+                )),
+                Some(_) => {}
+            },
+            SimpleLicense::Ref {
+                document_ref: Some(document_ref),
+                ..
+            } => {
+                if !document_refs.contains(document_ref.as_str()) {
+                    report(Diagnostic::error(
+                        path,
+                        "spdx-license",
+                        format!("Undefined external document {:?}", document_ref),
+                    ))
+                }
+            }
+            SimpleLicense::Ref { license_ref, .. } => {
+                if !license_refs.contains(license_ref.as_str()) {
+                    report(Diagnostic::error(
+                        path,
+                        "spdx-license",
+                        format!(
+                            "Undefined license reference {:?}, expected in hasExtractedLicensingInfos",
+                            license_ref
+                        ),
+                    ))
+                }
+            }
+        }
+        if let Some(exception) = &term.exception {
+            match lookup_exception(exception) {
+                None => report(Diagnostic::error(
+                    path,
+                    "spdx-license",
+                    format!("Unknown license exception identifier {:?}", exception),
+                )),
+                Some(listed) if listed.deprecated => report(Diagnostic::warning(
+                    path,
+                    "spdx-license",
+                    format!("Deprecated license exception identifier {:?}", listed.id),
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+    diagnostics
+}
+
+// Returns the paths and values of the SPDXIDs of the items of an array of the document.
+fn ids<'a>(
+    array: &'a str,
+    ids: impl Iterator<Item = &'a String> + 'a,
+) -> impl Iterator<Item = (String, &'a String)> + 'a {
+    ids.enumerate()
+        .map(move |(i, id)| (format!("/{}/{}/SPDXID", array, i), id))
+}
+
+// Returns why an SPDXID is invalid, if it is.
+fn check_spdxid(id: &str, is_document: bool) -> Option<String> {
+    if is_document {
+        return (id != DOCUMENT_SPDXID)
+            .then(|| format!("Invalid SPDXID {:?}, expected {:?}", id, DOCUMENT_SPDXID));
+    }
+    let valid = id.strip_prefix(SPDXID_PREFIX).is_some_and(|name| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    });
+    (!valid).then(|| {
+        format!(
+            "Invalid SPDXID {:?}, expected \"SPDXRef-\" followed by letters, numbers, . or -",
+            id
+        )
+    })
+}
+
+// Returns why a document namespace is invalid, if it is.
+fn check_namespace(namespace: &str, external_namespaces: &[&str]) -> Option<String> {
+    if Url::parse(namespace).is_err() {
+        return Some(format!(
+            "Invalid documentNamespace {:?}, expected an absolute URI",
+            namespace
+        ));
+    }
+    if namespace.contains('#') {
+        return Some(format!(
+            "Invalid documentNamespace {:?}, expected a URI without a # fragment",
+            namespace
+        ));
+    }
+    if external_namespaces.contains(&namespace) {
+        return Some(format!(
+            "documentNamespace {:?} is not unique, it is used by an external document reference",
+            namespace
+        ));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::report::Severity;
+    use serde_json::json;
+
+    fn example() -> Value {
+        serde_json::from_str(include_str!("../../tests/fixtures/spdx_v23_example.json")).unwrap()
+    }
+
+    fn paths(value: &Value) -> Vec<(String, String)> {
+        validate_spdx23(value)
+            .unwrap_err()
+            .diagnostics
+            .into_iter()
+            .map(|d| (d.path, d.keyword))
+            .collect()
+    }
+
+    #[test]
+    fn valid_example() {
+        let (document, report) = validate_spdx23(&example()).unwrap();
+        assert!(report.diagnostics.is_empty());
+        assert!(check_spdx23(&document).is_empty());
+    }
+
+    #[test]
+    fn invalid_license_expressions() {
+        let mut value = example();
+        value["packages"][0]["licenseConcluded"] = json!("(LGPL-2.0-only OR LicenseRef-9)");
+        value["packages"][0]["licenseDeclared"] = json!("MIT OR");
+        value["packages"][0]["licenseInfoFromFiles"][0] = json!("Not-A-License");
+        value["files"][0]["licenseConcluded"] =
+            json!("GPL-2.0-or-later WITH Not-An-Exception AND DocumentRef-x:LicenseRef-1");
+        let diagnostics = validate_spdx23(&value).unwrap_err().diagnostics;
+        let messages = diagnostics
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "Undefined license reference \"LicenseRef-9\", expected in \
+                 hasExtractedLicensingInfos at /packages/0/licenseConcluded",
+                "Invalid license expression \"MIT OR\": expected a license, found the end of \
+                 the expression at /packages/0/licenseDeclared",
+                "Unknown license identifier \"Not-A-License\" at \
+                 /packages/0/licenseInfoFromFiles/0",
+                "Unknown license exception identifier \"Not-An-Exception\" at \
+                 /files/0/licenseConcluded",
+                "Undefined external document \"DocumentRef-x\" at /files/0/licenseConcluded",
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| d.keyword == "spdx-license"));
+    }
+
+    #[test]
+    fn deprecated_license_warnings() {
+        let mut value = example();
+        value["packages"][3]["licenseConcluded"] = json!("gpl-2.0+ WITH Classpath-exception-2.0");
+        let (_, report) = validate_spdx23(&value).unwrap();
+        let warnings = report.warnings().map(|d| d.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            ["Deprecated license identifier \"GPL-2.0\" at /packages/3/licenseConcluded"]
+        );
+    }
+
+    #[test]
+    fn invalid_creation_info() {
+        let mut value = example();
+        value["dataLicense"] = json!("MIT");
+        value["creationInfo"]["created"] = json!("2010-01-29 18:30");
+        value["documentNamespace"] = json!("http://spdx.org/spdxdocs/example#1");
+        assert_eq!(
+            paths(&value),
+            [
+                ("/dataLicense".to_string(), "spdx-data-license".to_string()),
+                (
+                    "/creationInfo/created".to_string(),
+                    "spdx-created".to_string()
+                ),
+                (
+                    "/documentNamespace".to_string(),
+                    "spdx-namespace".to_string()
+                ),
+            ]
+        );
+
+        for namespace in [
+            "spdx-example",
+            value["externalDocumentRefs"][0]["spdxDocument"]
+                .as_str()
+                .unwrap(),
+        ] {
+            let mut value = example();
+            value["documentNamespace"] = json!(namespace);
+            assert_eq!(paths(&value)[0].0, "/documentNamespace");
+        }
+    }
+
+    #[test]
+    fn invalid_spdxids() {
+        let mut value = example();
+        value["SPDXID"] = json!("SPDXRef-Document");
+        value["packages"][1]["SPDXID"] = json!("SPDXRef-Package");
+        value["files"][0]["SPDXID"] = json!("SPDXRef-Doap_Source");
+        value["snippets"][0]["SPDXID"] = json!("Snippet");
+        let diagnostics = validate_spdx23(&value).unwrap_err().diagnostics;
+        let messages = diagnostics
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages[..4],
+            [
+                "Invalid SPDXID \"SPDXRef-Document\", expected \"SPDXRef-DOCUMENT\" at /SPDXID",
+                "Duplicate SPDXID \"SPDXRef-Package\" (first defined at /packages/0/SPDXID) at \
+                 /packages/1/SPDXID",
+                "Invalid SPDXID \"SPDXRef-Doap_Source\", expected \"SPDXRef-\" followed by \
+                 letters, numbers, . or - at /files/0/SPDXID",
+                "Invalid SPDXID \"Snippet\", expected \"SPDXRef-\" followed by letters, \
+                 numbers, . or - at /snippets/0/SPDXID",
+            ]
+        );
+    }
+
+    #[test]
+    fn skipped_semantic_checks() {
+        let mut value = example();
+        value["dataLicense"] = json!("MIT");
+        value["packages"][0]["downloadLocation"] = json!(42);
+        let diagnostics = validate_spdx23(&value).unwrap_err().diagnostics;
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.path != "/dataLicense" && d.keyword != "spdx-data-license"));
+        let skipped = diagnostics.last().unwrap();
+        assert_eq!(skipped.keyword, "spdx-skipped");
+        assert_eq!(skipped.severity, Severity::Warning);
+    }
+
+    #[test]
+    fn undefined_relationship_elements() {
+        let mut value = example();
+        value["relationships"][0]["spdxElementId"] = json!("SPDXRef-Missing");
+        value["relationships"][1]["relatedSpdxElement"] =
+            json!("DocumentRef-missing:SPDXRef-ToolsElement");
+        value["relationships"][3]["relatedSpdxElement"] = json!("NONE");
+        value["relationships"][4]["spdxElementId"] = json!("NOASSERTION");
+        assert_eq!(
+            paths(&value),
+            [
+                (
+                    "/relationships/0/spdxElementId".to_string(),
+                    "spdx-reference".to_string()
+                ),
+                (
+                    "/relationships/1/relatedSpdxElement".to_string(),
+                    "spdx-reference".to_string()
+                ),
+                (
+                    "/relationships/4/spdxElementId".to_string(),
+                    "spdx-reference".to_string()
+                ),
+            ]
+        );
+    }
+}
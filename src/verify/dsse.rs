@@ -0,0 +1,194 @@
+//! Offline verification of DSSE envelope signatures.
+//!
+//! Signatures are verified over the DSSE pre-authentication encoding (PAE) of the
+//! envelope payload using locally supplied public keys. Only once the required
+//! threshold of keys has verified is the wrapped In-Toto statement returned.
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    crypto::keys::PublicKey,
+    models::{dsse::envelope::Envelope, intoto::statement::InTotoStatementV1},
+};
+
+/// A public key trusted to sign envelopes, along with a name used for reporting.
+#[derive(Debug, Clone)]
+pub struct TrustedKey {
+    /// A human readable name for the key, e.g. the path it was loaded from.
+    pub name: String,
+    pub key: PublicKey,
+}
+
+/// A signature in the envelope that was verified by a trusted key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedSignature {
+    /// The keyid of the signature in the envelope, if present.
+    pub keyid: Option<String>,
+    /// The name of the trusted key that verified the signature.
+    pub key_name: String,
+}
+
+/// The result of successfully verifying a DSSE envelope.
+#[derive(Debug)]
+pub struct VerifiedEnvelope {
+    pub statement: InTotoStatementV1,
+    pub verified_signatures: Vec<VerifiedSignature>,
+}
+
+/// Verifies the signatures of a DSSE envelope wrapping an In-Toto v1 statement.
+///
+/// Every signature is checked against every trusted key. The envelope is accepted when at
+/// least `threshold` distinct trusted keys have each verified a different signature, in which
+/// case the decoded statement is returned. Keys given more than once are counted once.
+pub fn verify_envelope(
+    envelope: &Envelope,
+    keys: &[TrustedKey],
+    threshold: usize,
+) -> Result<VerifiedEnvelope> {
+    if threshold == 0 {
+        return Err(anyhow!("Threshold must be at least 1"));
+    }
+    if envelope.signatures.is_empty() {
+        return Err(anyhow!("Envelope contains no signatures"));
+    }
+
+    let mut distinct_keys: Vec<&TrustedKey> = Vec::new();
+    for trusted in keys {
+        if !distinct_keys.iter().any(|k| k.key == trusted.key) {
+            distinct_keys.push(trusted);
+        }
+    }
+
+    let pae = envelope.pae();
+    let mut verified_signatures = Vec::new();
+    let mut verified_keys: Vec<&PublicKey> = Vec::new();
+
+    // Each signature counts toward the threshold once, for the first key verifying it that
+    // hasn't verified another signature.
+    for signature in &envelope.signatures {
+        let trusted = distinct_keys.iter().find(|trusted| {
+            !verified_keys.contains(&&trusted.key)
+                && trusted.key.verify(&pae, &signature.sig).is_ok()
+        });
+        if let Some(trusted) = trusted {
+            verified_keys.push(&trusted.key);
+            verified_signatures.push(VerifiedSignature {
+                keyid: signature.keyid.clone(),
+                key_name: trusted.name.clone(),
+            });
+        }
+    }
+
+    if verified_keys.len() < threshold {
+        return Err(anyhow!(
+            "Signature threshold not met: {} of {} required key(s) verified",
+            verified_keys.len(),
+            threshold
+        ));
+    }
+
+    let statement = envelope.statement()?;
+    Ok(VerifiedEnvelope {
+        statement,
+        verified_signatures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dsse::envelope::Signature;
+
+    const SIGNED_ENVELOPE: &str =
+        include_str!("../../tests/fixtures/dsse_slsa_provenance_v1_signed.json");
+    const ED25519_PUBLIC_KEY: &str = include_str!("../../tests/fixtures/keys/ed25519.pub.pem");
+    const ECDSA_P256_PUBLIC_KEY: &str =
+        include_str!("../../tests/fixtures/keys/ecdsa_p256.pub.pem");
+    const RSA_PUBLIC_KEY: &str = include_str!("../../tests/fixtures/keys/rsa.pub.pem");
+
+    fn trusted_key(name: &str, pem: &str) -> TrustedKey {
+        TrustedKey {
+            name: name.to_string(),
+            key: PublicKey::from_pem(pem).unwrap(),
+        }
+    }
+
+    fn signed_envelope() -> Envelope {
+        serde_json::from_str(SIGNED_ENVELOPE).unwrap()
+    }
+
+    #[test]
+    fn verify_envelope_all_key_types() {
+        let keys = vec![
+            trusted_key("ed25519", ED25519_PUBLIC_KEY),
+            trusted_key("ecdsa", ECDSA_P256_PUBLIC_KEY),
+            trusted_key("rsa", RSA_PUBLIC_KEY),
+        ];
+
+        let verified = verify_envelope(&signed_envelope(), &keys, 3).unwrap();
+        let keyids = verified
+            .verified_signatures
+            .iter()
+            .map(|s| s.keyid.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(keyids, vec!["ed25519-key", "ecdsa-p256-key", "rsa-pss-key"]);
+        assert_eq!(verified.statement.subject.len(), 1);
+    }
+
+    #[test]
+    fn verify_envelope_threshold_not_met() {
+        let keys = vec![trusted_key("ed25519", ED25519_PUBLIC_KEY)];
+
+        let result = verify_envelope(&signed_envelope(), &keys, 2);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("1 of 2 required key(s) verified"));
+    }
+
+    #[test]
+    fn verify_envelope_duplicate_keys() {
+        let keys = vec![
+            trusted_key("ed25519", ED25519_PUBLIC_KEY),
+            trusted_key("ed25519 again", ED25519_PUBLIC_KEY),
+        ];
+        let result = verify_envelope(&signed_envelope(), &keys, 2);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("1 of 2 required key(s) verified"));
+
+        // A signature repeated in the envelope is only counted once.
+        let mut envelope = signed_envelope();
+        let first = envelope.signatures.remove(0);
+        envelope.signatures = (0..2)
+            .map(|_| Signature {
+                keyid: first.keyid.clone(),
+                sig: first.sig.clone(),
+            })
+            .collect();
+        let keys = vec![
+            trusted_key("ed25519", ED25519_PUBLIC_KEY),
+            trusted_key("ecdsa", ECDSA_P256_PUBLIC_KEY),
+        ];
+        assert!(verify_envelope(&envelope, &keys, 2).is_err());
+        let verified = verify_envelope(&envelope, &keys, 1).unwrap();
+        assert_eq!(verified.verified_signatures.len(), 1);
+    }
+
+    #[test]
+    fn verify_envelope_tampered_payload() {
+        let keys = vec![trusted_key("ed25519", ED25519_PUBLIC_KEY)];
+        let mut envelope = signed_envelope();
+        envelope.payload.push(b' ');
+
+        assert!(verify_envelope(&envelope, &keys, 1).is_err());
+    }
+
+    #[test]
+    fn verify_envelope_zero_threshold() {
+        let keys = vec![trusted_key("ed25519", ED25519_PUBLIC_KEY)];
+
+        assert!(verify_envelope(&signed_envelope(), &keys, 0).is_err());
+    }
+}
@@ -0,0 +1,9 @@
+//! Verification of supply chain metadata documents.
+//!
+//! Unlike validation, which only checks that a document is well-formed, verification
+//! checks a document against external material such as public keys or local artifacts.
+
+pub mod dsse;
+pub mod provenance;
+pub mod slsa_level;
+pub mod subject;
@@ -0,0 +1,372 @@
+//! Verification of SLSA provenance against an expected-builder policy.
+//!
+//! This implements the SLSA "verifying artifacts" flow: the provenance must come from a
+//! trusted builder, use an accepted build type, have the expected external parameters,
+//! e.g. the source repository and ref, and its subjects must match the artifacts.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{
+    models::intoto::{predicate::Predicate, statement::InTotoStatementV1},
+    verify::subject::verify_subjects,
+};
+
+/// A policy describing the provenance expected for an artifact.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvenancePolicy {
+    /// Builder IDs trusted to produce the provenance.
+    ///
+    /// A trusted builder ID also matches builder IDs with an `@<ref>` suffix, e.g.
+    /// `https://example.com/builder.yml` matches `https://example.com/builder.yml@refs/tags/v1`.
+    #[serde(rename = "trustedBuilders", default)]
+    pub trusted_builders: Vec<String>,
+    /// Accepted build types. Any build type is accepted when empty.
+    #[serde(rename = "buildTypes", default)]
+    pub build_types: Vec<String>,
+    /// Expected values of external parameters, keyed by a dot-separated path,
+    /// e.g. `workflow.repository`, or a JSON pointer, e.g. `/workflow/repository`.
+    #[serde(rename = "externalParameters", default)]
+    pub external_parameters: BTreeMap<String, Value>,
+    /// Accept provenance without artifacts to match its subjects against. Otherwise the
+    /// subject check fails, as the provenance isn't bound to any artifact.
+    #[serde(rename = "allowNoSubject", default)]
+    pub allow_no_subject: bool,
+}
+
+/// The status of a single policy check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    /// The check was not applicable, e.g. no artifacts were given to match subjects against.
+    Skipped,
+}
+
+impl std::fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckStatus::Passed => f.write_str("PASSED"),
+            CheckStatus::Failed => f.write_str("FAILED"),
+            CheckStatus::Skipped => f.write_str("SKIPPED"),
+        }
+    }
+}
+
+/// The result of a single policy check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckResult {
+    /// The name of the check, e.g. `builder.id` or `externalParameters.workflow.ref`.
+    pub name: String,
+    pub status: CheckStatus,
+    /// A human readable explanation of the result.
+    pub message: String,
+}
+
+/// The result of verifying provenance against a policy, with a result per check.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProvenanceVerificationReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl ProvenanceVerificationReport {
+    /// Returns true if no check failed.
+    pub fn is_success(&self) -> bool {
+        self.failed().count() == 0
+    }
+
+    /// Returns the checks that failed.
+    pub fn failed(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|c| c.status == CheckStatus::Failed)
+    }
+
+    fn push(&mut self, name: impl Into<String>, status: CheckStatus, message: String) {
+        self.checks.push(CheckResult {
+            name: name.into(),
+            status,
+            message,
+        });
+    }
+}
+
+/// Verifies the SLSA Provenance v1 predicate of a statement against a policy.
+///
+/// Subjects are matched against `artifacts`. Without artifacts, the subject check fails
+/// unless the policy allows it, in which case it is skipped. Fails if the statement doesn't
+/// have a SLSA Provenance v1 predicate or an artifact can't be read; policy violations are
+/// reported as failed checks instead.
+pub fn verify_provenance(
+    statement: &InTotoStatementV1,
+    policy: &ProvenancePolicy,
+    artifacts: &[PathBuf],
+) -> Result<ProvenanceVerificationReport> {
+    let provenance = match &statement.predicate {
+        Predicate::SLSAProvenanceV1(provenance) => provenance,
+        _ => {
+            return Err(anyhow!(
+                "Unexpected predicateType: {:?}",
+                statement.predicate_type.as_str()
+            ))
+        }
+    };
+    let mut report = ProvenanceVerificationReport::default();
+
+    let builder_id = provenance.run_details.builder.id.as_str();
+    if policy.trusted_builders.is_empty() {
+        report.push(
+            "builder.id",
+            CheckStatus::Failed,
+            "No trusted builders configured".to_string(),
+        );
+    } else if policy
+        .trusted_builders
+        .iter()
+        .any(|trusted| builder_matches(trusted, builder_id))
+    {
+        report.push(
+            "builder.id",
+            CheckStatus::Passed,
+            format!("Builder {:?} is trusted", builder_id),
+        );
+    } else {
+        report.push(
+            "builder.id",
+            CheckStatus::Failed,
+            format!("Builder {:?} is not trusted", builder_id),
+        );
+    }
+
+    let build_type = provenance.build_definition.build_type.as_str();
+    if policy.build_types.is_empty() {
+        report.push(
+            "buildType",
+            CheckStatus::Skipped,
+            "No accepted build types configured".to_string(),
+        );
+    } else if policy.build_types.iter().any(|t| t == build_type) {
+        report.push(
+            "buildType",
+            CheckStatus::Passed,
+            format!("Build type {:?} is accepted", build_type),
+        );
+    } else {
+        report.push(
+            "buildType",
+            CheckStatus::Failed,
+            format!("Build type {:?} is not accepted", build_type),
+        );
+    }
+
+    let external_parameters = &provenance.build_definition.external_parameters;
+    for (key, expected) in &policy.external_parameters {
+        let name = if key.starts_with('/') {
+            format!("externalParameters{}", key)
+        } else {
+            format!("externalParameters.{}", key)
+        };
+        match lookup_parameter(external_parameters, key) {
+            Some(actual) if actual == expected => report.push(
+                name,
+                CheckStatus::Passed,
+                format!("{} matches the expected value", actual),
+            ),
+            Some(actual) => report.push(
+                name,
+                CheckStatus::Failed,
+                format!("Expected {}, found {}", expected, actual),
+            ),
+            None => report.push(
+                name,
+                CheckStatus::Failed,
+                format!("Expected {}, found no value", expected),
+            ),
+        }
+    }
+
+    if artifacts.is_empty() {
+        let status = match policy.allow_no_subject {
+            true => CheckStatus::Skipped,
+            false => CheckStatus::Failed,
+        };
+        report.push("subject", status, "No artifacts given".to_string());
+    } else {
+        let subjects = verify_subjects(&statement.subject, artifacts)?;
+        if subjects.is_success() {
+            report.push(
+                "subject",
+                CheckStatus::Passed,
+                format!("{} artifact(s) matched a subject", artifacts.len()),
+            );
+        } else {
+            report.push(
+                "subject",
+                CheckStatus::Failed,
+                format!(
+                    "{} unmatched artifact(s), {} digest mismatch(es)",
+                    subjects.unmatched_artifacts.len(),
+                    subjects.mismatches.len()
+                ),
+            );
+        }
+    }
+
+    Ok(report)
+}
+
+// Returns true if the builder ID equals the trusted builder ID, optionally followed by a ref.
+pub(crate) fn builder_matches(trusted: &str, builder_id: &str) -> bool {
+    match builder_id.strip_prefix(trusted) {
+        Some(rest) => rest.is_empty() || (rest.starts_with('@') && !trusted.contains('@')),
+        None => false,
+    }
+}
+
+// Looks up an external parameter by JSON pointer or dot-separated path.
+fn lookup_parameter<'a>(parameters: &'a Value, key: &str) -> Option<&'a Value> {
+    if key.starts_with('/') {
+        return parameters.pointer(key);
+    }
+    key.split('.')
+        .try_fold(parameters, |value, segment| value.get(segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use serde_json::json;
+
+    const STATEMENT: &str = include_str!("../../tests/fixtures/slsa_provenance_v1.json");
+    const BUILDER_ID: &str =
+        "https://github.com/slsa-framework/slsa-github-generator/.github/workflows/builder_go_slsa3.yml";
+    const BUILD_TYPE: &str =
+        "https://slsa-framework.github.io/github-actions-buildtypes/workflow/v1";
+
+    fn statement() -> InTotoStatementV1 {
+        serde_json::from_str(STATEMENT).unwrap()
+    }
+
+    fn policy() -> ProvenancePolicy {
+        ProvenancePolicy {
+            trusted_builders: vec![BUILDER_ID.to_string()],
+            build_types: vec![BUILD_TYPE.to_string()],
+            external_parameters: BTreeMap::from([
+                (
+                    "workflow.repository".to_string(),
+                    json!("https://github.com/octocat/hello-world"),
+                ),
+                ("/workflow/ref".to_string(), json!("refs/heads/main")),
+            ]),
+            allow_no_subject: true,
+        }
+    }
+
+    fn status(report: &ProvenanceVerificationReport, name: &str) -> CheckStatus {
+        report
+            .checks
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn verify_provenance_passes() {
+        let report = verify_provenance(&statement(), &policy(), &[]).unwrap();
+
+        assert!(report.is_success());
+        assert_eq!(status(&report, "builder.id"), CheckStatus::Passed);
+        assert_eq!(status(&report, "buildType"), CheckStatus::Passed);
+        assert_eq!(
+            status(&report, "externalParameters.workflow.repository"),
+            CheckStatus::Passed
+        );
+        assert_eq!(
+            status(&report, "externalParameters/workflow/ref"),
+            CheckStatus::Passed
+        );
+        assert_eq!(status(&report, "subject"), CheckStatus::Skipped);
+    }
+
+    #[test]
+    fn verify_provenance_untrusted_builder() {
+        let mut policy = policy();
+        policy.trusted_builders = vec!["https://example.com/builder".to_string()];
+
+        let report = verify_provenance(&statement(), &policy, &[]).unwrap();
+        assert!(!report.is_success());
+        assert_eq!(status(&report, "builder.id"), CheckStatus::Failed);
+        assert_eq!(report.failed().count(), 1);
+    }
+
+    #[test]
+    fn verify_provenance_no_trusted_builders() {
+        let report = verify_provenance(&statement(), &ProvenancePolicy::default(), &[]).unwrap();
+        assert_eq!(status(&report, "builder.id"), CheckStatus::Failed);
+        assert_eq!(status(&report, "buildType"), CheckStatus::Skipped);
+    }
+
+    #[test]
+    fn verify_provenance_no_artifacts() {
+        let mut policy = policy();
+        policy.allow_no_subject = false;
+
+        let report = verify_provenance(&statement(), &policy, &[]).unwrap();
+        assert!(!report.is_success());
+        assert_eq!(status(&report, "subject"), CheckStatus::Failed);
+    }
+
+    #[test]
+    fn verify_provenance_unexpected_parameters() {
+        let mut policy = policy();
+        policy
+            .external_parameters
+            .insert("workflow.ref".to_string(), json!("refs/heads/dev"));
+        policy
+            .external_parameters
+            .insert("inputs.missing".to_string(), json!(true));
+        policy.build_types = vec!["https://example.com/buildType".to_string()];
+
+        let report = verify_provenance(&statement(), &policy, &[]).unwrap();
+        assert_eq!(
+            status(&report, "externalParameters.workflow.ref"),
+            CheckStatus::Failed
+        );
+        assert_eq!(
+            status(&report, "externalParameters.inputs.missing"),
+            CheckStatus::Failed
+        );
+        assert_eq!(status(&report, "buildType"), CheckStatus::Failed);
+        assert_eq!(report.failed().count(), 3);
+    }
+
+    #[test]
+    fn verify_provenance_unmatched_artifact() {
+        let dir = TempDir::new("provenance_unmatched_artifact");
+        let artifact = dir.write("hello.txt", b"hello\n");
+
+        let report = verify_provenance(&statement(), &policy(), &[artifact]).unwrap();
+        assert_eq!(status(&report, "subject"), CheckStatus::Failed);
+    }
+
+    #[test]
+    fn builder_id_matching() {
+        assert!(builder_matches(BUILDER_ID, BUILDER_ID));
+        assert!(builder_matches(
+            BUILDER_ID,
+            &format!("{}@refs/tags/v0.0.1", BUILDER_ID)
+        ));
+        assert!(!builder_matches(
+            &format!("{}@refs/tags/v0.0.1", BUILDER_ID),
+            &format!("{}@refs/tags/v0.0.1-rc", BUILDER_ID)
+        ));
+        assert!(!builder_matches(
+            BUILDER_ID,
+            &format!("{}.evil", BUILDER_ID)
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+//! Assessment of the SLSA Build level evidenced by provenance.
+//!
+//! Most SLSA Build track requirements are properties of the build platform rather than of
+//! the provenance document, so the assessment relies on configuration describing the highest
+//! level each trusted builder is known to meet. The provenance itself evidences Build L1, and
+//! a verified signature evidences the authenticity required from Build L2.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    models::intoto::{
+        predicate::Predicate,
+        provenance::ResourceDescriptor,
+        statement::InTotoStatementV1,
+        vsa::{SLSAVerificationSummaryV1Predicate, VerificationResult, Verifier},
+    },
+    verify::provenance::builder_matches,
+};
+
+/// The highest SLSA Build level assessed.
+pub const MAX_BUILD_LEVEL: u8 = 3;
+
+/// Configuration describing which builders are trusted at which SLSA Build level.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildLevelConfig {
+    #[serde(default)]
+    pub builders: Vec<TrustedBuilder>,
+}
+
+/// A builder trusted to meet the build platform requirements up to a SLSA Build level.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrustedBuilder {
+    /// The builder ID, also matching builder IDs with an `@<ref>` suffix.
+    pub id: String,
+    /// The highest SLSA Build level the builder meets.
+    pub level: u8,
+}
+
+impl BuildLevelConfig {
+    /// Returns the highest level any matching trusted builder is configured at, if any.
+    pub fn builder_level(&self, builder_id: &str) -> Option<u8> {
+        self.builders
+            .iter()
+            .filter(|b| builder_matches(&b.id, builder_id))
+            .map(|b| b.level)
+            .max()
+    }
+}
+
+/// A SLSA Build track requirement and whether it was evidenced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    /// The lowest level requiring this requirement.
+    pub level: u8,
+    pub name: String,
+    pub satisfied: bool,
+    /// A human readable explanation of why the requirement was or wasn't evidenced.
+    pub explanation: String,
+}
+
+/// The SLSA Build level evidenced by provenance, with the requirements that determined it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildLevelAssessment {
+    pub level: u8,
+    pub requirements: Vec<Requirement>,
+}
+
+impl BuildLevelAssessment {
+    /// Returns the verified levels, in the format of a VSA `verifiedLevels` list.
+    pub fn verified_levels(&self) -> Vec<String> {
+        vec![format!("SLSA_BUILD_LEVEL_{}", self.level)]
+    }
+
+    /// Creates a VSA predicate summarizing this assessment.
+    ///
+    /// The verification result is `PASSED` when at least Build L1 was evidenced.
+    pub fn to_vsa(
+        &self,
+        verifier_id: Url,
+        resource_uri: Url,
+        policy: ResourceDescriptor,
+    ) -> SLSAVerificationSummaryV1Predicate {
+        SLSAVerificationSummaryV1Predicate {
+            verifier: Verifier {
+                id: verifier_id,
+                version: None,
+            },
+            time_verified: Utc::now(),
+            resource_uri,
+            policy,
+            input_attestations: None,
+            verification_result: if self.level > 0 {
+                VerificationResult::Passed
+            } else {
+                VerificationResult::Failed
+            },
+            verified_levels: self.verified_levels(),
+            dependency_levels: None,
+            slsa_version: Some("1.0".to_string()),
+        }
+    }
+}
+
+/// Assesses the SLSA Build level evidenced by a statement.
+///
+/// `signature_verified` should be true only if the statement was extracted from a DSSE
+/// envelope whose signature was verified against a key trusted for the builder.
+pub fn assess_build_level(
+    statement: &InTotoStatementV1,
+    config: &BuildLevelConfig,
+    signature_verified: bool,
+) -> BuildLevelAssessment {
+    let mut requirements = Vec::new();
+    let mut requirement = |level: u8, name: &str, satisfied: bool, explanation: String| {
+        requirements.push(Requirement {
+            level,
+            name: name.to_string(),
+            satisfied,
+            explanation,
+        })
+    };
+
+    let builder_id = match &statement.predicate {
+        Predicate::SLSAProvenanceV1(provenance) => {
+            requirement(
+                1,
+                "Provenance exists",
+                true,
+                format!(
+                    "SLSA Provenance v1 identifies builder {:?} and build type {:?}",
+                    provenance.run_details.builder.id.as_str(),
+                    provenance.build_definition.build_type.as_str()
+                ),
+            );
+            Some(provenance.run_details.builder.id.as_str())
+        }
+        _ => {
+            requirement(
+                1,
+                "Provenance exists",
+                false,
+                format!(
+                    "Predicate type {:?} is not SLSA Provenance v1",
+                    statement.predicate_type.as_str()
+                ),
+            );
+            None
+        }
+    };
+
+    let builder_level = builder_id.and_then(|id| config.builder_level(id));
+    let builder_explanation = |level: u8| match (builder_id, builder_level) {
+        (None, _) => "No builder identified".to_string(),
+        (Some(id), None) => format!("Builder {:?} is not trusted at any level", id),
+        (Some(id), Some(trusted)) if trusted >= level => {
+            format!("Builder {:?} is trusted at level {}", id, trusted)
+        }
+        (Some(id), Some(trusted)) => {
+            format!("Builder {:?} is only trusted at level {}", id, trusted)
+        }
+    };
+
+    requirement(
+        2,
+        "Hosted build platform",
+        builder_level.is_some_and(|l| l >= 2),
+        builder_explanation(2),
+    );
+    requirement(
+        2,
+        "Provenance is authentic",
+        signature_verified,
+        if signature_verified {
+            "Provenance signature was verified".to_string()
+        } else {
+            "Provenance signature was not verified".to_string()
+        },
+    );
+    requirement(
+        3,
+        "Hardened build platform",
+        builder_level.is_some_and(|l| l >= 3),
+        builder_explanation(3),
+    );
+
+    let level = (1..=MAX_BUILD_LEVEL)
+        .take_while(|level| {
+            requirements
+                .iter()
+                .filter(|r| r.level <= *level)
+                .all(|r| r.satisfied)
+        })
+        .last()
+        .unwrap_or(0);
+
+    BuildLevelAssessment {
+        level,
+        requirements,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATEMENT: &str = include_str!("../../tests/fixtures/slsa_provenance_v1.json");
+    const BUILDER_ID: &str =
+        "https://github.com/slsa-framework/slsa-github-generator/.github/workflows/builder_go_slsa3.yml";
+
+    fn statement() -> InTotoStatementV1 {
+        serde_json::from_str(STATEMENT).unwrap()
+    }
+
+    fn config(level: u8) -> BuildLevelConfig {
+        BuildLevelConfig {
+            builders: vec![TrustedBuilder {
+                id: BUILDER_ID.to_string(),
+                level,
+            }],
+        }
+    }
+
+    #[test]
+    fn assess_build_level_3() {
+        let assessment = assess_build_level(&statement(), &config(3), true);
+        assert_eq!(assessment.level, 3);
+        assert!(assessment.requirements.iter().all(|r| r.satisfied));
+        assert_eq!(assessment.verified_levels(), vec!["SLSA_BUILD_LEVEL_3"]);
+    }
+
+    #[test]
+    fn assess_build_level_unsigned() {
+        let assessment = assess_build_level(&statement(), &config(3), false);
+        assert_eq!(assessment.level, 1);
+
+        let unsatisfied = assessment
+            .requirements
+            .iter()
+            .filter(|r| !r.satisfied)
+            .map(|r| r.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(unsatisfied, vec!["Provenance is authentic"]);
+    }
+
+    #[test]
+    fn assess_build_level_limited_by_builder() {
+        let assessment = assess_build_level(&statement(), &config(2), true);
+        assert_eq!(assessment.level, 2);
+
+        let assessment = assess_build_level(&statement(), &BuildLevelConfig::default(), true);
+        assert_eq!(assessment.level, 1);
+        assert!(assessment.requirements[1]
+            .explanation
+            .contains("is not trusted at any level"));
+    }
+
+    #[test]
+    fn assess_build_level_0() {
+        let statement = serde_json::from_str::<InTotoStatementV1>(include_str!(
+            "../../tests/fixtures/slsa_vsa_v1.json"
+        ))
+        .unwrap();
+
+        let assessment = assess_build_level(&statement, &config(3), true);
+        assert_eq!(assessment.level, 0);
+        assert_eq!(assessment.verified_levels(), vec!["SLSA_BUILD_LEVEL_0"]);
+    }
+
+    #[test]
+    fn assessment_to_vsa() {
+        let assessment = assess_build_level(&statement(), &config(3), true);
+        let policy =
+            ResourceDescriptor::new(Some("https://example.com/policy".parse().unwrap()), None);
+
+        let vsa = assessment.to_vsa(
+            Url::parse("https://example.com/verifier").unwrap(),
+            Url::parse("pkg:npm/hello-world@1.0.0").unwrap(),
+            policy,
+        );
+        assert_eq!(vsa.verification_result, VerificationResult::Passed);
+        assert_eq!(vsa.verified_levels, vec!["SLSA_BUILD_LEVEL_3"]);
+    }
+}
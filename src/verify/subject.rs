@@ -0,0 +1,238 @@
+//! Verification of statement subjects against local artifacts.
+//!
+//! Each artifact is hashed with every algorithm present in the subjects' digest sets and
+//! compared, so that an attestation can be tied to the exact files about to be shipped.
+
+use anyhow::Result;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    crypto::digest::{digest_file, DigestRequest},
+    models::intoto::statement::{Algorithm, Subject},
+};
+
+/// A subject whose digests all matched an artifact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectMatch {
+    pub subject: String,
+    pub artifact: PathBuf,
+}
+
+/// A digest of a subject that disagreed with the digest computed for an artifact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestMismatch {
+    pub subject: String,
+    pub artifact: PathBuf,
+    pub algorithm: Algorithm,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// The result of verifying statement subjects against local artifacts.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SubjectVerificationReport {
+    /// Subjects that matched an artifact on every computable digest.
+    pub matched: Vec<SubjectMatch>,
+    /// Digests that disagreed for an artifact that partially matched a subject or shares its name.
+    pub mismatches: Vec<DigestMismatch>,
+    /// Artifacts that did not match any subject.
+    pub unmatched_artifacts: Vec<PathBuf>,
+    /// Subjects that did not match any artifact.
+    pub unmatched_subjects: Vec<String>,
+}
+
+impl SubjectVerificationReport {
+    /// Returns true if every artifact matched a subject and no digests disagreed.
+    pub fn is_success(&self) -> bool {
+        self.unmatched_artifacts.is_empty() && self.mismatches.is_empty()
+    }
+}
+
+/// Verifies the given artifacts against the subjects of a statement.
+///
+/// A subject matches an artifact when every digest that can be computed from a file agrees.
+/// When only some digests agree, or the artifact's file name equals the subject's name but no
+/// digests agree, the disagreeing digests are reported as mismatches.
+pub fn verify_subjects(
+    subjects: &[Subject],
+    artifacts: &[PathBuf],
+) -> Result<SubjectVerificationReport> {
+    let requests = subjects
+        .iter()
+        .flat_map(|subject| subject.digest.iter())
+        .map(|(algorithm, digest)| DigestRequest {
+            algorithm: algorithm.clone(),
+            hex_len: digest.len(),
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let mut report = SubjectVerificationReport::default();
+    let mut matched_subjects = HashSet::new();
+
+    for artifact in artifacts {
+        let computed = digest_file(artifact, &requests)?;
+        let mut artifact_matched = false;
+        let mut artifact_mismatched = false;
+
+        for (index, subject) in subjects.iter().enumerate() {
+            let mut agreed = 0;
+            let mut disagreed = Vec::new();
+            for (algorithm, expected) in subject.digest.iter() {
+                let request = DigestRequest {
+                    algorithm: algorithm.clone(),
+                    hex_len: expected.len(),
+                };
+                match computed.get(&request) {
+                    Some(actual) if actual == expected => agreed += 1,
+                    Some(actual) => disagreed.push(DigestMismatch {
+                        subject: subject.name.clone(),
+                        artifact: artifact.clone(),
+                        algorithm: algorithm.clone(),
+                        expected: expected.to_string(),
+                        actual: actual.clone(),
+                    }),
+                    None => {}
+                }
+            }
+
+            if agreed > 0 && disagreed.is_empty() {
+                artifact_matched = true;
+                matched_subjects.insert(index);
+                report.matched.push(SubjectMatch {
+                    subject: subject.name.clone(),
+                    artifact: artifact.clone(),
+                });
+            } else if !disagreed.is_empty() && (agreed > 0 || same_name(subject, artifact)) {
+                artifact_mismatched = true;
+                report.mismatches.extend(disagreed);
+            }
+        }
+
+        if !artifact_matched && !artifact_mismatched {
+            report.unmatched_artifacts.push(artifact.clone());
+        }
+    }
+
+    report.unmatched_subjects = subjects
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !matched_subjects.contains(index))
+        .map(|(_, subject)| subject.name.clone())
+        .collect();
+
+    Ok(report)
+}
+
+// Returns true if the artifact's file name equals the last path component of the subject name.
+fn same_name(subject: &Subject, artifact: &Path) -> bool {
+    match (Path::new(&subject.name).file_name(), artifact.file_name()) {
+        (Some(subject_name), Some(artifact_name)) => subject_name == artifact_name,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::intoto::statement::DigestSet, test_util::TempDir};
+    use maplit::hashmap;
+
+    const HELLO_SHA256: &str = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+    const HELLO_SHA1: &str = "f572d396fae9206628714fb2ce00f72e94f2258f";
+    const OTHER_SHA256: &str = "fe4fe40ac7250263c5dbe1cf3138912f3f416140aa248637a60d65fe22c47da4";
+
+    fn subject(name: &str, digests: std::collections::HashMap<Algorithm, String>) -> Subject {
+        Subject {
+            name: name.to_string(),
+            digest: DigestSet::new(digests).unwrap(),
+        }
+    }
+
+    // Writes an artifact in a directory of its own, removed when the directory is dropped.
+    fn write_artifact(name: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new(&format!("subject_{}", name));
+        let path = dir.write(name, b"hello\n");
+        (dir, path)
+    }
+
+    #[test]
+    fn verify_matching_subject() {
+        let (_dir, artifact) = write_artifact("matching.txt");
+        let subjects = vec![
+            subject(
+                "dist/hello.txt",
+                hashmap! {
+                    Algorithm::Sha256 => HELLO_SHA256.to_string(),
+                    Algorithm::Sha1 => HELLO_SHA1.to_string(),
+                    Algorithm::GitCommit => HELLO_SHA1.to_string(),
+                },
+            ),
+            subject(
+                "other",
+                hashmap! {Algorithm::Sha256 => OTHER_SHA256.to_string()},
+            ),
+        ];
+
+        let report = verify_subjects(&subjects, std::slice::from_ref(&artifact)).unwrap();
+        assert!(report.is_success());
+        assert_eq!(
+            report.matched,
+            vec![SubjectMatch {
+                subject: "dist/hello.txt".to_string(),
+                artifact,
+            }]
+        );
+        assert_eq!(report.unmatched_subjects, vec!["other".to_string()]);
+    }
+
+    #[test]
+    fn verify_partially_matching_subject() {
+        let (_dir, artifact) = write_artifact("partial.txt");
+        let subjects = vec![subject(
+            "hello",
+            hashmap! {
+                Algorithm::Sha256 => HELLO_SHA256.to_string(),
+                Algorithm::Sha1 => "0000000000000000000000000000000000000000".to_string(),
+            },
+        )];
+
+        let report = verify_subjects(&subjects, &[artifact]).unwrap();
+        assert!(!report.is_success());
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].algorithm, Algorithm::Sha1);
+        assert_eq!(report.mismatches[0].actual, HELLO_SHA1);
+    }
+
+    #[test]
+    fn verify_same_name_mismatch() {
+        let (_dir, artifact) = write_artifact("release.tar.gz");
+        let subjects = vec![subject(
+            "out/release.tar.gz",
+            hashmap! {Algorithm::Sha256 => OTHER_SHA256.to_string()},
+        )];
+
+        let report = verify_subjects(&subjects, &[artifact]).unwrap();
+        assert!(report.unmatched_artifacts.is_empty());
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].expected, OTHER_SHA256);
+    }
+
+    #[test]
+    fn verify_unmatched_artifact() {
+        let (_dir, artifact) = write_artifact("unmatched.txt");
+        let subjects = vec![subject(
+            "other",
+            hashmap! {Algorithm::Sha256 => OTHER_SHA256.to_string()},
+        )];
+
+        let report = verify_subjects(&subjects, std::slice::from_ref(&artifact)).unwrap();
+        assert!(!report.is_success());
+        assert_eq!(report.unmatched_artifacts, vec![artifact]);
+        assert!(report.mismatches.is_empty());
+    }
+}
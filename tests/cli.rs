@@ -14,7 +14,7 @@ fn test_valid_slsa_provenance_v1_document() {
     let mut cmd = Command::cargo_bin("spector").unwrap();
     let fixture = fixture_path("slsa_provenance_v1.json");
 
-    cmd.args(&[
+    cmd.args([
         "validate",
         "in-toto-v1",
         "--file",
@@ -27,12 +27,345 @@ fn test_valid_slsa_provenance_v1_document() {
     ));
 }
 
+#[test]
+fn test_invalid_slsa_provenance_v1_reports_all_errors() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1_multiple_errors.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "subject[0]: missing field `name` at /subject/0",
+    ))
+    .stderr(predicate::str::contains(
+        "42 is not of type \"string\" at /predicate/runDetails/metadata/startedOn",
+    ))
+    .stderr(predicate::str::contains(
+        "at /predicate/buildDefinition/resolvedDependencies/0/digest",
+    ))
+    .stderr(predicate::str::contains("Error: 3 problem(s) found"));
+}
+
+#[test]
+fn test_valid_slsa_provenance_v1_json_output() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1.json");
+
+    let output = cmd
+        .args([
+            "validate",
+            "--output",
+            "json",
+            "in-toto-v1",
+            "--file",
+            fixture.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = serde_json::from_slice::<serde_json::Value>(&output).unwrap();
+    assert_eq!(output["valid"], true);
+    assert_eq!(output["documents"][0]["diagnostics"], serde_json::json!([]));
+}
+
+#[test]
+fn test_invalid_slsa_provenance_v1_sarif_output() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1_multiple_errors.json");
+
+    let output = cmd
+        .args([
+            "validate",
+            "in-toto-v1",
+            "--file",
+            fixture.to_str().unwrap(),
+            "--output",
+            "sarif",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Error: 3 problem(s) found"))
+        .get_output()
+        .stdout
+        .clone();
+    let output = serde_json::from_slice::<serde_json::Value>(&output).unwrap();
+    assert_eq!(output["version"], "2.1.0");
+    let results = output["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+
+    let started_on = results
+        .iter()
+        .find(|r| r["ruleId"] == "type")
+        .expect("type error for startedOn");
+    let region = &started_on["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 46);
+    assert_eq!(region["startColumn"], 30);
+}
+
+#[test]
+fn test_schema_validate_json_output() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let schema = fixture_path("in_toto_v1_schema.json");
+    let fixture = fixture_path("slsa_provenance_v1_multiple_errors.json");
+
+    let output = cmd
+        .args([
+            "schema-validate",
+            schema.to_str().unwrap(),
+            "--file",
+            fixture.to_str().unwrap(),
+            "--output",
+            "json",
+        ])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let output = serde_json::from_slice::<serde_json::Value>(&output).unwrap();
+    let diagnostics = &output["documents"][0]["diagnostics"];
+    assert_eq!(diagnostics[0]["path"], "/subject/0");
+    assert_eq!(diagnostics[0]["keyword"], "required");
+}
+
+#[test]
+fn test_validate_slsa_provenance_v1_from_stdin() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = std::fs::read_to_string(fixture_path("slsa_provenance_v1.json")).unwrap();
+
+    cmd.args(["validate", "in-toto-v1", "--file", "-"])
+        .write_stdin(fixture)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Valid InTotoV1 SLSAProvenanceV1 document",
+        ));
+}
+
+#[test]
+fn test_validate_multiple_files() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let valid = fixture_path("slsa_provenance_v1.json");
+    let invalid = fixture_path("slsa_provenance_v1_invalid.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        valid.to_str().unwrap(),
+        "--file",
+        invalid.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stdout(predicate::str::contains(format!(
+        "{}: valid",
+        valid.display()
+    )))
+    .stderr(predicate::str::contains(format!(
+        "{}: invalid: 2 problem(s) found",
+        invalid.display()
+    )))
+    .stdout(predicate::str::contains("1 of 2 file(s) valid"))
+    .stderr(predicate::str::contains(
+        "Error: 1 of 2 file(s) failed validation",
+    ));
+}
+
+#[test]
+fn test_validate_glob_pattern_json_output() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let pattern = fixture_path("slsa_provenance_v1_invalid*.json");
+
+    let output = cmd
+        .args([
+            "validate",
+            "--output",
+            "json",
+            "in-toto-v1",
+            pattern.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let output = serde_json::from_slice::<serde_json::Value>(&output).unwrap();
+    assert_eq!(output["summary"]["files"], 4);
+    assert_eq!(output["summary"]["invalid"], 3);
+    assert!(output["documents"][0]["file"]
+        .as_str()
+        .unwrap()
+        .ends_with("slsa_provenance_v1_invalid.json"));
+}
+
+#[test]
+fn test_validate_jobs_deterministic_output() {
+    let pattern = fixture_path("*.json");
+    let run = |jobs: &str| {
+        Command::cargo_bin("spector")
+            .unwrap()
+            .args([
+                "validate",
+                "--output",
+                "json",
+                "--jobs",
+                jobs,
+                "in-toto-v1",
+                pattern.to_str().unwrap(),
+            ])
+            .assert()
+            .failure()
+            .get_output()
+            .stdout
+            .clone()
+    };
+
+    let sequential = run("1");
+    assert_eq!(run("4"), sequential);
+    let output = serde_json::from_slice::<serde_json::Value>(&sequential).unwrap();
+    let files = output["documents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["file"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    let mut sorted = files.clone();
+    sorted.sort();
+    assert_eq!(files, sorted);
+}
+
+#[test]
+fn test_validate_spdx_v23() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    cmd.args([
+        "validate",
+        "spdx-v23",
+        "--file",
+        fixture_path("spdx_v23_example.json").to_str().unwrap(),
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Valid document"));
+
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    cmd.args([
+        "validate",
+        "spdx-v23",
+        "--file",
+        fixture_path("spdx_v23_invalid.json").to_str().unwrap(),
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid dataLicense \"MIT\", expected \"CC0-1.0\" at /dataLicense",
+        ))
+        .stderr(predicate::str::contains("at /creationInfo/created"))
+        .stderr(predicate::str::contains(
+            "Duplicate SPDXID \"SPDXRef-Package\" (first defined at /packages/0/SPDXID) at \
+             /packages/1/SPDXID",
+        ))
+        .stderr(predicate::str::contains(
+            "Undefined SPDX element \"SPDXRef-Missing\" at /relationships/0/relatedSpdxElement",
+        ))
+        .stderr(predicate::str::contains(
+            "Unknown license identifier \"Not-A-License\" at /packages/3/licenseConcluded",
+        ))
+        .stderr(predicate::str::contains("Error: 5 problem(s) found"));
+}
+
+#[test]
+fn test_validate_auto() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    cmd.args([
+        "validate",
+        "auto",
+        "--file",
+        fixture_path("dsse_slsa_provenance_v1.json")
+            .to_str()
+            .unwrap(),
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Detected DSSE envelope document"))
+        .stdout(predicate::str::contains("Valid document"));
+
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    cmd.args([
+        "validate",
+        "auto",
+        "--file",
+        fixture_path("slsa_provenance_v1_invalid.json")
+            .to_str()
+            .unwrap(),
+    ]);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Detected In-Toto v1 statement document",
+        ))
+        .stderr(predicate::str::contains(
+            "\"runDetails\" is a required property at /predicate",
+        ));
+}
+
+#[test]
+fn test_validate_auto_unknown_document() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    cmd.args([
+        "validate",
+        "--output",
+        "json",
+        "auto",
+        fixture_path("slsa_level_config.json").to_str().unwrap(),
+        fixture_path("slsa_provenance_v1_legacy_type.json")
+            .to_str()
+            .unwrap(),
+    ]);
+    let output = cmd.assert().failure().get_output().stdout.clone();
+    let output = serde_json::from_slice::<serde_json::Value>(&output).unwrap();
+    assert_eq!(
+        output["documents"][0]["diagnostics"][0]["keyword"],
+        "detect"
+    );
+    assert_eq!(
+        output["documents"][1]["documentKind"],
+        "In-Toto v0.1 statement"
+    );
+    assert_eq!(output["documents"][1]["valid"], true);
+}
+
+#[test]
+fn test_invalid_slsa_provenance_v1_build_type_parameters() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1_invalid_parameters.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Invalid externalParameters: missing field `repository`",
+    ));
+}
+
 #[test]
 fn test_invalid_slsa_provenance_v1_document() {
     let mut cmd = Command::cargo_bin("spector").unwrap();
     let fixture = fixture_path("slsa_provenance_v1_invalid.json");
 
-    cmd.args(&[
+    cmd.args([
         "validate",
         "in-toto-v1",
         "--file",
@@ -50,7 +383,7 @@ fn test_invalid_predicate_slsa_provenance_v1_document() {
     let mut cmd = Command::cargo_bin("spector").unwrap();
     let fixture = fixture_path("slsa_provenance_v1_invalid_predicate.json");
 
-    cmd.args(&[
+    cmd.args([
         "validate",
         "in-toto-v1",
         "--predicate",
@@ -70,7 +403,7 @@ fn test_generate_in_toto_v1_schema() {
     let mut cmd = Command::cargo_bin("spector").unwrap();
     let fixture = std::fs::read_to_string(fixture_path("in_toto_v1_schema.json")).unwrap();
 
-    cmd.args(&["schema-generate", "in-toto-v1"])
+    cmd.args(["schema-generate", "in-toto-v1"])
         .assert()
         .success()
         .stdout(predicate::str::contains(fixture));
@@ -81,19 +414,685 @@ fn test_generate_slsa_provenance_v1_schema() {
     let mut cmd = Command::cargo_bin("spector").unwrap();
     let fixture = std::fs::read_to_string(fixture_path("slsa_provenance_v1_schema.json")).unwrap();
 
-    cmd.args(&["schema-generate", "in-toto-v1", "--predicate", "slsa-provenance-v1"])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains(fixture));
+    cmd.args([
+        "schema-generate",
+        "in-toto-v1",
+        "--predicate",
+        "slsa-provenance-v1",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(fixture));
 }
 
 #[test]
 fn test_generate_rust_code() {
     let mut cmd = Command::cargo_bin("spector").unwrap();
+    // The fixture is the exact output of the generator, so regenerate it with the same
+    // command when the generator's formatting changes.
     let fixture = std::fs::read_to_string(fixture_path("in_toto_v1.rs")).unwrap();
 
-    cmd.args(&["code-generate", "json-schema", "--file", "tests/fixtures/in_toto_v1_schema.json"])
+    cmd.args([
+        "code-generate",
+        "json-schema",
+        "--file",
+        "tests/fixtures/in_toto_v1_schema.json",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(fixture));
+}
+
+#[test]
+fn test_valid_dsse_slsa_provenance_v1_document() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("dsse_slsa_provenance_v1.json");
+
+    cmd.args([
+        "validate",
+        "dsse",
+        "--predicate",
+        "slsa-provenance-v1",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Valid DSSE envelope with 1 signature(s)",
+    ))
+    .stdout(predicate::str::contains(
+        "Valid InTotoV1 SLSAProvenanceV1 document",
+    ));
+}
+
+#[test]
+fn test_invalid_dsse_payload_type() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("dsse_invalid_payload_type.json");
+
+    cmd.args(["validate", "dsse", "--file", fixture.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unexpected payloadType: \"application/json\"",
+        ));
+}
+
+#[test]
+fn test_verify_dsse_signatures() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("dsse_slsa_provenance_v1_signed.json");
+    let ed25519 = fixture_path("keys/ed25519.pub.pem");
+    let ecdsa = fixture_path("keys/ecdsa_p256.pub.pem");
+    let rsa = fixture_path("keys/rsa.pub.pem");
+
+    cmd.args([
+        "verify",
+        "--key",
+        ed25519.to_str().unwrap(),
+        "--key",
+        ecdsa.to_str().unwrap(),
+        "--key",
+        rsa.to_str().unwrap(),
+        "--threshold",
+        "3",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Verified signature with keyid \"ed25519-key\"",
+    ))
+    .stdout(predicate::str::contains(
+        "Verified signature with keyid \"ecdsa-p256-key\"",
+    ))
+    .stdout(predicate::str::contains(
+        "Verified signature with keyid \"rsa-pss-key\"",
+    ))
+    .stdout(predicate::str::contains(
+        "Valid InTotoV1 SLSAProvenanceV1 document",
+    ));
+}
+
+#[test]
+fn test_verify_dsse_threshold_not_met() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("dsse_slsa_provenance_v1_signed.json");
+    let ed25519 = fixture_path("keys/ed25519.pub.pem");
+
+    cmd.args([
+        "verify",
+        "--key",
+        ed25519.to_str().unwrap(),
+        "--threshold",
+        "2",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Signature threshold not met: 1 of 2 required key(s) verified",
+    ));
+}
+
+#[test]
+fn test_verify_dsse_duplicate_keys() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("dsse_slsa_provenance_v1_signed.json");
+    let ed25519 = fixture_path("keys/ed25519.pub.pem");
+
+    cmd.args([
+        "verify",
+        "--key",
+        ed25519.to_str().unwrap(),
+        "--key",
+        ed25519.to_str().unwrap(),
+        "--threshold",
+        "2",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Signature threshold not met: 1 of 2 required key(s) verified",
+    ));
+}
+
+#[test]
+fn test_verify_unsigned_dsse() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("dsse_slsa_provenance_v1.json");
+    let ed25519 = fixture_path("keys/ed25519.pub.pem");
+
+    cmd.args([
+        "verify",
+        "--key",
+        ed25519.to_str().unwrap(),
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Signature threshold not met"));
+}
+
+#[test]
+fn test_sign_statement_then_verify() {
+    let statement = fixture_path("slsa_provenance_v1.json");
+    let key = fixture_path("keys/ecdsa_p256.pem");
+    let public_key = fixture_path("keys/ecdsa_p256.pub.pem");
+
+    let output = Command::cargo_bin("spector")
+        .unwrap()
+        .args([
+            "sign",
+            "--key",
+            key.to_str().unwrap(),
+            "--keyid",
+            "release-key",
+            "--file",
+            statement.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // A directory of its own keeps concurrent test runs from sharing the envelope.
+    let dir = std::env::temp_dir().join(format!(
+        "spector_test_sign_statement_then_verify_{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let envelope_path = dir.join("envelope.json");
+    std::fs::write(&envelope_path, &output.stdout).unwrap();
+
+    let assert = Command::cargo_bin("spector")
+        .unwrap()
+        .args([
+            "verify",
+            "--key",
+            public_key.to_str().unwrap(),
+            "--file",
+            envelope_path.to_str().unwrap(),
+        ])
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert.success().stdout(predicate::str::contains(
+        "Verified signature with keyid \"release-key\"",
+    ));
+}
+
+#[test]
+fn test_sign_with_unsupported_key() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let statement = fixture_path("slsa_provenance_v1.json");
+    let key = fixture_path("keys/rsa.pub.pem");
+
+    cmd.args([
+        "sign",
+        "--key",
+        key.to_str().unwrap(),
+        "--file",
+        statement.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Unsupported private key"));
+}
+
+#[test]
+fn test_legacy_statement_type_strict() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1_legacy_type.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Invalid _type: \"https://in-toto.io/Statement/v0.1\"",
+    ));
+}
+
+#[test]
+fn test_legacy_statement_type_lenient() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1_legacy_type.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--lenient",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("Warning: Legacy _type"))
+    .stdout(predicate::str::contains(
+        "Valid InTotoV1 SLSAProvenanceV1 document",
+    ));
+}
+
+#[test]
+fn test_invalid_subject_digest() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1_invalid_digest.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "subject[0]: invalid sha256 digest \"invalid_digest\"",
+    ));
+}
+
+#[test]
+fn test_verify_subject_matches() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let statement = fixture_path("artifacts_statement.json");
+    let artifact = fixture_path("artifacts/hello.txt");
+
+    cmd.args([
+        "verify-subject",
+        "--statement",
+        statement.to_str().unwrap(),
+        artifact.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Matched subject \"hello.txt\""))
+    .stderr(predicate::str::contains(
+        "Unmatched subject: \"goodbye.txt\"",
+    ));
+}
+
+#[test]
+fn test_verify_subject_mismatch() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let statement = fixture_path("artifacts_statement.json");
+    let hello = fixture_path("artifacts/hello.txt");
+    let goodbye = fixture_path("artifacts/goodbye.txt");
+
+    cmd.args([
+        "verify-subject",
+        "--statement",
+        statement.to_str().unwrap(),
+        hello.to_str().unwrap(),
+        goodbye.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Digest mismatch for subject \"goodbye.txt\"",
+    ))
+    .stderr(predicate::str::contains(
+        "Subject verification failed: 0 unmatched artifact(s), 1 digest mismatch(es)",
+    ));
+}
+
+#[test]
+fn test_predicate_by_uri() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--predicate",
+        "https://slsa.dev/provenance/v1",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Valid InTotoV1 SLSAProvenanceV1 document",
+    ));
+}
+
+#[test]
+fn test_unknown_predicate() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v1.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--predicate",
+        "unknown-predicate",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "unknown predicate type \"unknown-predicate\"",
+    ));
+}
+
+#[test]
+fn test_convert_slsa_v02_to_v1() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v02.json");
+
+    cmd.args([
+        "convert",
+        "slsa-v0.2-to-v1",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "\"predicateType\": \"https://slsa.dev/provenance/v1\"",
+    ))
+    .stdout(predicate::str::contains(
+        "\"invocationId\": \"1536140711-1\"",
+    ))
+    .stderr(predicate::str::contains(
+        "Warning: metadata.completeness has no SLSA Provenance v1 equivalent",
+    ));
+}
+
+#[test]
+fn test_validate_slsa_v02_lenient() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_provenance_v02.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--lenient",
+        "--predicate",
+        "slsa-provenance-v0.2",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Valid InTotoV1 SLSAProvenanceV02 document",
+    ));
+}
+
+#[test]
+fn test_generate_slsa_vsa_v1_schema() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = std::fs::read_to_string(fixture_path("slsa_vsa_v1_schema.json")).unwrap();
+
+    cmd.args([
+        "schema-generate",
+        "in-toto-v1",
+        "--predicate",
+        "slsa-vsa-v1",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(fixture));
+}
+
+#[test]
+fn test_valid_slsa_vsa_v1_document() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let fixture = fixture_path("slsa_vsa_v1.json");
+
+    cmd.args([
+        "validate",
+        "in-toto-v1",
+        "--predicate",
+        "slsa-vsa-v1",
+        "--file",
+        fixture.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Valid InTotoV1 SLSAVerificationSummaryV1 document",
+    ));
+}
+
+#[test]
+fn test_verify_provenance_policy() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let statement = fixture_path("slsa_provenance_v1.json");
+    let policy = fixture_path("provenance_policy.json");
+
+    cmd.args([
+        "verify-provenance",
+        "--statement",
+        statement.to_str().unwrap(),
+        "--policy",
+        policy.to_str().unwrap(),
+        "--allow-no-subject",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("PASSED builder.id"))
+    .stdout(predicate::str::contains(
+        "PASSED externalParameters.workflow.ref",
+    ))
+    .stdout(predicate::str::contains("Provenance verification passed"));
+}
+
+#[test]
+fn test_verify_provenance_policy_violation() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let statement = fixture_path("slsa_provenance_v1.json");
+
+    cmd.args([
+        "verify-provenance",
+        "--statement",
+        statement.to_str().unwrap(),
+        "--builder-id",
+        "https://example.com/builder",
+        "--external-parameter",
+        "workflow.ref=refs/heads/dev",
+    ])
+    .assert()
+    .failure()
+    .stdout(predicate::str::contains("FAILED builder.id"))
+    .stdout(predicate::str::contains(
+        "FAILED externalParameters.workflow.ref: Expected \"refs/heads/dev\", found \"refs/heads/main\"",
+    ))
+    .stderr(predicate::str::contains(
+        "Provenance verification failed: 3 of 4 check(s) failed",
+    ));
+}
+
+#[test]
+fn test_verify_provenance_requires_subject() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let statement = fixture_path("slsa_provenance_v1.json");
+    let policy = fixture_path("provenance_policy.json");
+
+    cmd.args([
+        "verify-provenance",
+        "--statement",
+        statement.to_str().unwrap(),
+        "--policy",
+        policy.to_str().unwrap(),
+    ])
+    .assert()
+    .failure()
+    .stdout(predicate::str::contains(
+        "FAILED subject: No artifacts given",
+    ))
+    .stderr(predicate::str::contains(
+        "Provenance verification failed: 1 of 5 check(s) failed",
+    ));
+}
+
+#[test]
+fn test_verify_provenance_envelope_signatures() {
+    let statement = fixture_path("dsse_slsa_provenance_v1_signed.json");
+    let policy = fixture_path("provenance_policy.json");
+    let args = [
+        "verify-provenance",
+        "--statement",
+        statement.to_str().unwrap(),
+        "--policy",
+        policy.to_str().unwrap(),
+        "--allow-no-subject",
+    ];
+
+    Command::cargo_bin("spector")
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "is a DSSE envelope, pass --key to verify its signatures",
+        ));
+
+    let ed25519 = fixture_path("keys/ed25519.pub.pem");
+    Command::cargo_bin("spector")
+        .unwrap()
+        .args(args)
+        .args(["--key", ed25519.to_str().unwrap()])
         .assert()
         .success()
-        .stdout(predicate::str::contains(fixture));
+        .stdout(predicate::str::contains(
+            "PASSED signature: 1 signature(s) verified",
+        ))
+        .stdout(predicate::str::contains("Provenance verification passed"));
+}
+
+#[test]
+fn test_slsa_level_signed_provenance() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let statement = fixture_path("dsse_slsa_provenance_v1_signed.json");
+    let config = fixture_path("slsa_level_config.json");
+    let key = fixture_path("keys/ed25519.pub.pem");
+
+    cmd.args([
+        "slsa-level",
+        "--statement",
+        statement.to_str().unwrap(),
+        "--config",
+        config.to_str().unwrap(),
+        "--key",
+        key.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "EVIDENCED L2 Provenance is authentic",
+    ))
+    .stdout(predicate::str::contains("SLSA Build level: 3"));
+}
+
+#[test]
+fn test_slsa_level_vsa() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let statement = fixture_path("slsa_provenance_v1.json");
+    let config = fixture_path("slsa_level_config.json");
+
+    let output = cmd
+        .args([
+            "slsa-level",
+            "--statement",
+            statement.to_str().unwrap(),
+            "--config",
+            config.to_str().unwrap(),
+            "--vsa",
+            "--verifier-id",
+            "https://example.com/verifier",
+            "--resource-uri",
+            "pkg:npm/hello-world@1.0.0",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // The unsigned provenance only evidences Build L1.
+    let vsa: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        vsa["predicateType"],
+        "https://slsa.dev/verification_summary/v1"
+    );
+    assert_eq!(
+        vsa["predicate"]["verifiedLevels"],
+        serde_json::json!(["SLSA_BUILD_LEVEL_1"])
+    );
+}
+
+#[test]
+fn test_generate_provenance_github_actions() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let artifact = fixture_path("artifacts/hello.txt");
+
+    let output = cmd
+        .env_clear()
+        .envs([
+            ("GITHUB_SERVER_URL", "https://github.com"),
+            ("GITHUB_REPOSITORY", "octocat/hello-world"),
+            ("GITHUB_REF", "refs/heads/main"),
+            ("GITHUB_SHA", "c27d339ee6075c1f744c5d4b200f7901aad2c369"),
+            ("GITHUB_RUN_ID", "1536140711"),
+            ("GITHUB_RUN_ATTEMPT", "1"),
+            ("GITHUB_EVENT_NAME", "push"),
+            (
+                "GITHUB_WORKFLOW_REF",
+                "octocat/hello-world/.github/workflows/release.yml@refs/heads/main",
+            ),
+        ])
+        .args([
+            "generate",
+            "provenance",
+            "--ci",
+            "github-actions",
+            artifact.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let statement: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(statement["subject"][0]["name"], "hello.txt");
+    assert_eq!(
+        statement["subject"][0]["digest"]["sha256"],
+        "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03"
+    );
+    assert_eq!(
+        statement["predicate"]["buildDefinition"]["externalParameters"]["workflow"]["path"],
+        ".github/workflows/release.yml"
+    );
+    assert_eq!(
+        statement["predicate"]["runDetails"]["builder"]["id"],
+        "https://github.com/actions/runner/github-hosted"
+    );
+}
+
+#[test]
+fn test_generate_provenance_missing_environment() {
+    let mut cmd = Command::cargo_bin("spector").unwrap();
+    let artifact = fixture_path("artifacts/hello.txt");
+
+    cmd.env_clear()
+        .args([
+            "generate",
+            "provenance",
+            "--ci",
+            "gitlab-ci",
+            artifact.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Missing environment variable CI_SERVER_URL",
+        ));
 }
@@ -48,6 +48,46 @@ impl Builder {
         builder::Builder::default()
     }
 }
+///A structure representing the completeness claims of the SLSA Provenance v0.2 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct Completeness {
+    #[serde(default)]
+    pub environment: bool,
+    #[serde(default)]
+    pub materials: bool,
+    #[serde(default)]
+    pub parameters: bool,
+}
+impl From<&Completeness> for Completeness {
+    fn from(value: &Completeness) -> Self {
+        value.clone()
+    }
+}
+impl Completeness {
+    pub fn builder() -> builder::Completeness {
+        builder::Completeness::default()
+    }
+}
+///A structure representing the config source of the SLSA Provenance v0.2 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ConfigSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<DigestSet>,
+    #[serde(rename = "entryPoint", default, skip_serializing_if = "Option::is_none")]
+    pub entry_point: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+}
+impl From<&ConfigSource> for ConfigSource {
+    fn from(value: &ConfigSource) -> Self {
+        value.clone()
+    }
+}
+impl ConfigSource {
+    pub fn builder() -> builder::ConfigSource {
+        builder::ConfigSource::default()
+    }
+}
 #[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct DigestSet(pub std::collections::HashMap<String, String>);
 impl std::ops::Deref for DigestSet {
@@ -91,6 +131,44 @@ impl InTotoStatementV1 {
         builder::InTotoStatementV1::default()
     }
 }
+///A structure representing the invocation of the SLSA Provenance v0.2 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct Invocation {
+    #[serde(rename = "configSource", default, skip_serializing_if = "Option::is_none")]
+    pub config_source: Option<ConfigSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+}
+impl From<&Invocation> for Invocation {
+    fn from(value: &Invocation) -> Self {
+        value.clone()
+    }
+}
+impl Invocation {
+    pub fn builder() -> builder::Invocation {
+        builder::Invocation::default()
+    }
+}
+///A structure representing a material of the SLSA Provenance v0.2 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct Material {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<DigestSet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+}
+impl From<&Material> for Material {
+    fn from(value: &Material) -> Self {
+        value.clone()
+    }
+}
+impl Material {
+    pub fn builder() -> builder::Material {
+        builder::Material::default()
+    }
+}
 ///A structure representing the metadata of the SLSA Provenance v1 Predicate.
 #[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct Metadata {
@@ -113,15 +191,17 @@ impl Metadata {
 }
 /**An enum representing different predicate types.
 
-Known predicate types have their own variants, while unknown types are represented by the `Other` variant, which stores the raw JSON value.
-
-TODO(mlieberman85): Support (de)serializing the predicates based on the predicateType URL in the statement.*/
+Known predicate types have their own variants, predicate types registered through the `registry` module are represented by the `Registered` variant, while unknown types are represented by the `Other` variant, which stores the raw JSON value.*/
 #[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct Predicate {
     #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
     pub subtype_0: Option<SlsaProvenanceV1Predicate>,
     #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
-    pub subtype_1: Option<serde_json::Value>,
+    pub subtype_1: Option<SlsaProvenanceV02Predicate>,
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    pub subtype_2: Option<SlsaVerificationSummaryV1Predicate>,
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    pub subtype_3: Option<serde_json::Value>,
 }
 impl From<&Predicate> for Predicate {
     fn from(value: &Predicate) -> Self {
@@ -133,7 +213,9 @@ impl Predicate {
         builder::Predicate::default()
     }
 }
-///A structure representing a resource descriptor in the SLSA Provenance v1 Predicate.
+/**A structure representing a resource descriptor in the SLSA Provenance v1 Predicate.
+
+At least one of `uri`, `digest` or `content` must be set.*/
 #[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct ResourceDescriptor {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -141,7 +223,7 @@ pub struct ResourceDescriptor {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub digest: Option<std::collections::HashMap<String, String>>,
+    pub digest: Option<DigestSet>,
     #[serde(
         rename = "downloadLocation",
         default,
@@ -152,7 +234,8 @@ pub struct ResourceDescriptor {
     pub media_type: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
-    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
 }
 impl From<&ResourceDescriptor> for ResourceDescriptor {
     fn from(value: &ResourceDescriptor) -> Self {
@@ -182,6 +265,78 @@ impl RunDetails {
         builder::RunDetails::default()
     }
 }
+///A structure representing the builder of the SLSA Provenance v0.2 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SlsaProvenanceV02Builder {
+    pub id: String,
+}
+impl From<&SlsaProvenanceV02Builder> for SlsaProvenanceV02Builder {
+    fn from(value: &SlsaProvenanceV02Builder) -> Self {
+        value.clone()
+    }
+}
+impl SlsaProvenanceV02Builder {
+    pub fn builder() -> builder::SlsaProvenanceV02Builder {
+        builder::SlsaProvenanceV02Builder::default()
+    }
+}
+///A structure representing the metadata of the SLSA Provenance v0.2 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SlsaProvenanceV02Metadata {
+    #[serde(
+        rename = "buildFinishedOn",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_finished_on: Option<chrono::DateTime<chrono::offset::Utc>>,
+    #[serde(
+        rename = "buildInvocationId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_invocation_id: Option<String>,
+    #[serde(rename = "buildStartedOn", default, skip_serializing_if = "Option::is_none")]
+    pub build_started_on: Option<chrono::DateTime<chrono::offset::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completeness: Option<Completeness>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reproducible: Option<bool>,
+}
+impl From<&SlsaProvenanceV02Metadata> for SlsaProvenanceV02Metadata {
+    fn from(value: &SlsaProvenanceV02Metadata) -> Self {
+        value.clone()
+    }
+}
+impl SlsaProvenanceV02Metadata {
+    pub fn builder() -> builder::SlsaProvenanceV02Metadata {
+        builder::SlsaProvenanceV02Metadata::default()
+    }
+}
+///A structure representing the SLSA Provenance v0.2 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SlsaProvenanceV02Predicate {
+    #[serde(rename = "buildConfig", default, skip_serializing_if = "Option::is_none")]
+    pub build_config: Option<serde_json::Value>,
+    #[serde(rename = "buildType")]
+    pub build_type: String,
+    pub builder: SlsaProvenanceV02Builder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation: Option<Invocation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub materials: Option<Vec<Material>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<SlsaProvenanceV02Metadata>,
+}
+impl From<&SlsaProvenanceV02Predicate> for SlsaProvenanceV02Predicate {
+    fn from(value: &SlsaProvenanceV02Predicate) -> Self {
+        value.clone()
+    }
+}
+impl SlsaProvenanceV02Predicate {
+    pub fn builder() -> builder::SlsaProvenanceV02Predicate {
+        builder::SlsaProvenanceV02Predicate::default()
+    }
+}
 ///A structure representing the SLSA Provenance v1 Predicate.
 #[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct SlsaProvenanceV1Predicate {
@@ -200,6 +355,44 @@ impl SlsaProvenanceV1Predicate {
         builder::SlsaProvenanceV1Predicate::default()
     }
 }
+///A structure representing the SLSA Verification Summary v1 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SlsaVerificationSummaryV1Predicate {
+    #[serde(
+        rename = "dependencyLevels",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dependency_levels: Option<std::collections::HashMap<String, u64>>,
+    #[serde(
+        rename = "inputAttestations",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub input_attestations: Option<Vec<ResourceDescriptor>>,
+    pub policy: ResourceDescriptor,
+    #[serde(rename = "resourceUri")]
+    pub resource_uri: String,
+    #[serde(rename = "slsaVersion", default, skip_serializing_if = "Option::is_none")]
+    pub slsa_version: Option<String>,
+    #[serde(rename = "timeVerified")]
+    pub time_verified: chrono::DateTime<chrono::offset::Utc>,
+    #[serde(rename = "verificationResult")]
+    pub verification_result: VerificationResult,
+    #[serde(rename = "verifiedLevels")]
+    pub verified_levels: Vec<String>,
+    pub verifier: Verifier,
+}
+impl From<&SlsaVerificationSummaryV1Predicate> for SlsaVerificationSummaryV1Predicate {
+    fn from(value: &SlsaVerificationSummaryV1Predicate) -> Self {
+        value.clone()
+    }
+}
+impl SlsaVerificationSummaryV1Predicate {
+    pub fn builder() -> builder::SlsaVerificationSummaryV1Predicate {
+        builder::SlsaVerificationSummaryV1Predicate::default()
+    }
+}
 ///Represents a subject in an In-Toto v1 statement.
 #[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct Subject {
@@ -216,6 +409,84 @@ impl Subject {
         builder::Subject::default()
     }
 }
+///The result of a verification summarized by the SLSA Verification Summary v1 Predicate.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    schemars::JsonSchema
+)]
+pub enum VerificationResult {
+    #[serde(rename = "PASSED")]
+    Passed,
+    #[serde(rename = "FAILED")]
+    Failed,
+}
+impl From<&VerificationResult> for VerificationResult {
+    fn from(value: &VerificationResult) -> Self {
+        value.clone()
+    }
+}
+impl ToString for VerificationResult {
+    fn to_string(&self) -> String {
+        match *self {
+            Self::Passed => "PASSED".to_string(),
+            Self::Failed => "FAILED".to_string(),
+        }
+    }
+}
+impl std::str::FromStr for VerificationResult {
+    type Err = &'static str;
+    fn from_str(value: &str) -> Result<Self, &'static str> {
+        match value {
+            "PASSED" => Ok(Self::Passed),
+            "FAILED" => Ok(Self::Failed),
+            _ => Err("invalid value"),
+        }
+    }
+}
+impl std::convert::TryFrom<&str> for VerificationResult {
+    type Error = &'static str;
+    fn try_from(value: &str) -> Result<Self, &'static str> {
+        value.parse()
+    }
+}
+impl std::convert::TryFrom<&String> for VerificationResult {
+    type Error = &'static str;
+    fn try_from(value: &String) -> Result<Self, &'static str> {
+        value.parse()
+    }
+}
+impl std::convert::TryFrom<String> for VerificationResult {
+    type Error = &'static str;
+    fn try_from(value: String) -> Result<Self, &'static str> {
+        value.parse()
+    }
+}
+///A structure representing the verifier of the SLSA Verification Summary v1 Predicate.
+#[derive(Clone, Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct Verifier {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<std::collections::HashMap<String, String>>,
+}
+impl From<&Verifier> for Verifier {
+    fn from(value: &Verifier) -> Self {
+        value.clone()
+    }
+}
+impl Verifier {
+    pub fn builder() -> builder::Verifier {
+        builder::Verifier::default()
+    }
+}
 pub mod builder {
     #[derive(Clone, Debug)]
     pub struct BuildDefinition {
@@ -246,8 +517,7 @@ pub mod builder {
             T: std::convert::TryInto<String>,
             T::Error: std::fmt::Display,
         {
-            self
-                .build_type = value
+            self.build_type = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for build_type: {}", e)
@@ -259,8 +529,7 @@ pub mod builder {
             T: std::convert::TryInto<serde_json::Value>,
             T::Error: std::fmt::Display,
         {
-            self
-                .external_parameters = value
+            self.external_parameters = value
                 .try_into()
                 .map_err(|e| {
                     format!(
@@ -274,8 +543,7 @@ pub mod builder {
             T: std::convert::TryInto<serde_json::Value>,
             T::Error: std::fmt::Display,
         {
-            self
-                .internal_parameters = value
+            self.internal_parameters = value
                 .try_into()
                 .map_err(|e| {
                     format!(
@@ -289,8 +557,7 @@ pub mod builder {
             T: std::convert::TryInto<Vec<super::ResourceDescriptor>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .resolved_dependencies = value
+            self.resolved_dependencies = value
                 .try_into()
                 .map_err(|e| {
                     format!(
@@ -343,8 +610,7 @@ pub mod builder {
             T: std::convert::TryInto<Option<Vec<super::ResourceDescriptor>>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .builder_dependencies = value
+            self.builder_dependencies = value
                 .try_into()
                 .map_err(|e| {
                     format!(
@@ -358,8 +624,7 @@ pub mod builder {
             T: std::convert::TryInto<String>,
             T::Error: std::fmt::Display,
         {
-            self
-                .id = value
+            self.id = value
                 .try_into()
                 .map_err(|e| format!("error converting supplied value for id: {}", e));
             self
@@ -369,8 +634,7 @@ pub mod builder {
             T: std::convert::TryInto<Option<String>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .version = value
+            self.version = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for version: {}", e)
@@ -398,6 +662,148 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    pub struct Completeness {
+        environment: Result<bool, String>,
+        materials: Result<bool, String>,
+        parameters: Result<bool, String>,
+    }
+    impl Default for Completeness {
+        fn default() -> Self {
+            Self {
+                environment: Ok(Default::default()),
+                materials: Ok(Default::default()),
+                parameters: Ok(Default::default()),
+            }
+        }
+    }
+    impl Completeness {
+        pub fn environment<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<bool>,
+            T::Error: std::fmt::Display,
+        {
+            self.environment = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for environment: {}", e)
+                });
+            self
+        }
+        pub fn materials<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<bool>,
+            T::Error: std::fmt::Display,
+        {
+            self.materials = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for materials: {}", e)
+                });
+            self
+        }
+        pub fn parameters<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<bool>,
+            T::Error: std::fmt::Display,
+        {
+            self.parameters = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for parameters: {}", e)
+                });
+            self
+        }
+    }
+    impl std::convert::TryFrom<Completeness> for super::Completeness {
+        type Error = String;
+        fn try_from(value: Completeness) -> Result<Self, String> {
+            Ok(Self {
+                environment: value.environment?,
+                materials: value.materials?,
+                parameters: value.parameters?,
+            })
+        }
+    }
+    impl From<super::Completeness> for Completeness {
+        fn from(value: super::Completeness) -> Self {
+            Self {
+                environment: Ok(value.environment),
+                materials: Ok(value.materials),
+                parameters: Ok(value.parameters),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct ConfigSource {
+        digest: Result<Option<super::DigestSet>, String>,
+        entry_point: Result<Option<String>, String>,
+        uri: Result<Option<String>, String>,
+    }
+    impl Default for ConfigSource {
+        fn default() -> Self {
+            Self {
+                digest: Ok(Default::default()),
+                entry_point: Ok(Default::default()),
+                uri: Ok(Default::default()),
+            }
+        }
+    }
+    impl ConfigSource {
+        pub fn digest<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<super::DigestSet>>,
+            T::Error: std::fmt::Display,
+        {
+            self.digest = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for digest: {}", e)
+                });
+            self
+        }
+        pub fn entry_point<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<String>>,
+            T::Error: std::fmt::Display,
+        {
+            self.entry_point = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for entry_point: {}", e)
+                });
+            self
+        }
+        pub fn uri<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<String>>,
+            T::Error: std::fmt::Display,
+        {
+            self.uri = value
+                .try_into()
+                .map_err(|e| format!("error converting supplied value for uri: {}", e));
+            self
+        }
+    }
+    impl std::convert::TryFrom<ConfigSource> for super::ConfigSource {
+        type Error = String;
+        fn try_from(value: ConfigSource) -> Result<Self, String> {
+            Ok(Self {
+                digest: value.digest?,
+                entry_point: value.entry_point?,
+                uri: value.uri?,
+            })
+        }
+    }
+    impl From<super::ConfigSource> for ConfigSource {
+        fn from(value: super::ConfigSource) -> Self {
+            Self {
+                digest: Ok(value.digest),
+                entry_point: Ok(value.entry_point),
+                uri: Ok(value.uri),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct InTotoStatementV1 {
         predicate: Result<super::Predicate, String>,
         predicate_type: Result<String, String>,
@@ -420,8 +826,7 @@ pub mod builder {
             T: std::convert::TryInto<super::Predicate>,
             T::Error: std::fmt::Display,
         {
-            self
-                .predicate = value
+            self.predicate = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for predicate: {}", e)
@@ -433,8 +838,7 @@ pub mod builder {
             T: std::convert::TryInto<String>,
             T::Error: std::fmt::Display,
         {
-            self
-                .predicate_type = value
+            self.predicate_type = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for predicate_type: {}", e)
@@ -446,8 +850,7 @@ pub mod builder {
             T: std::convert::TryInto<Vec<super::Subject>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .subject = value
+            self.subject = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for subject: {}", e)
@@ -459,8 +862,7 @@ pub mod builder {
             T: std::convert::TryInto<String>,
             T::Error: std::fmt::Display,
         {
-            self
-                .type_ = value
+            self.type_ = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for type_: {}", e)
@@ -490,90 +892,217 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
-    pub struct Metadata {
-        finished_on: Result<Option<chrono::DateTime<chrono::offset::Utc>>, String>,
-        invocation_id: Result<String, String>,
-        started_on: Result<chrono::DateTime<chrono::offset::Utc>, String>,
+    pub struct Invocation {
+        config_source: Result<Option<super::ConfigSource>, String>,
+        environment: Result<Option<serde_json::Value>, String>,
+        parameters: Result<Option<serde_json::Value>, String>,
     }
-    impl Default for Metadata {
+    impl Default for Invocation {
         fn default() -> Self {
             Self {
-                finished_on: Ok(Default::default()),
-                invocation_id: Err("no value supplied for invocation_id".to_string()),
-                started_on: Err("no value supplied for started_on".to_string()),
+                config_source: Ok(Default::default()),
+                environment: Ok(Default::default()),
+                parameters: Ok(Default::default()),
             }
         }
     }
-    impl Metadata {
-        pub fn finished_on<T>(mut self, value: T) -> Self
+    impl Invocation {
+        pub fn config_source<T>(mut self, value: T) -> Self
         where
-            T: std::convert::TryInto<Option<chrono::DateTime<chrono::offset::Utc>>>,
+            T: std::convert::TryInto<Option<super::ConfigSource>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .finished_on = value
+            self.config_source = value
                 .try_into()
                 .map_err(|e| {
-                    format!("error converting supplied value for finished_on: {}", e)
+                    format!("error converting supplied value for config_source: {}", e)
                 });
             self
         }
-        pub fn invocation_id<T>(mut self, value: T) -> Self
+        pub fn environment<T>(mut self, value: T) -> Self
         where
-            T: std::convert::TryInto<String>,
+            T: std::convert::TryInto<Option<serde_json::Value>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .invocation_id = value
+            self.environment = value
                 .try_into()
                 .map_err(|e| {
-                    format!("error converting supplied value for invocation_id: {}", e)
+                    format!("error converting supplied value for environment: {}", e)
                 });
             self
         }
-        pub fn started_on<T>(mut self, value: T) -> Self
+        pub fn parameters<T>(mut self, value: T) -> Self
         where
-            T: std::convert::TryInto<chrono::DateTime<chrono::offset::Utc>>,
+            T: std::convert::TryInto<Option<serde_json::Value>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .started_on = value
+            self.parameters = value
                 .try_into()
                 .map_err(|e| {
-                    format!("error converting supplied value for started_on: {}", e)
+                    format!("error converting supplied value for parameters: {}", e)
                 });
             self
         }
     }
-    impl std::convert::TryFrom<Metadata> for super::Metadata {
+    impl std::convert::TryFrom<Invocation> for super::Invocation {
         type Error = String;
-        fn try_from(value: Metadata) -> Result<Self, String> {
+        fn try_from(value: Invocation) -> Result<Self, String> {
             Ok(Self {
-                finished_on: value.finished_on?,
-                invocation_id: value.invocation_id?,
-                started_on: value.started_on?,
+                config_source: value.config_source?,
+                environment: value.environment?,
+                parameters: value.parameters?,
             })
         }
     }
-    impl From<super::Metadata> for Metadata {
-        fn from(value: super::Metadata) -> Self {
+    impl From<super::Invocation> for Invocation {
+        fn from(value: super::Invocation) -> Self {
             Self {
-                finished_on: Ok(value.finished_on),
-                invocation_id: Ok(value.invocation_id),
-                started_on: Ok(value.started_on),
+                config_source: Ok(value.config_source),
+                environment: Ok(value.environment),
+                parameters: Ok(value.parameters),
             }
         }
     }
     #[derive(Clone, Debug)]
-    pub struct Predicate {
-        subtype_0: Result<Option<super::SlsaProvenanceV1Predicate>, String>,
-        subtype_1: Result<Option<serde_json::Value>, String>,
+    pub struct Material {
+        digest: Result<Option<super::DigestSet>, String>,
+        uri: Result<Option<String>, String>,
     }
-    impl Default for Predicate {
+    impl Default for Material {
         fn default() -> Self {
             Self {
-                subtype_0: Ok(Default::default()),
-                subtype_1: Ok(Default::default()),
+                digest: Ok(Default::default()),
+                uri: Ok(Default::default()),
+            }
+        }
+    }
+    impl Material {
+        pub fn digest<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<super::DigestSet>>,
+            T::Error: std::fmt::Display,
+        {
+            self.digest = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for digest: {}", e)
+                });
+            self
+        }
+        pub fn uri<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<String>>,
+            T::Error: std::fmt::Display,
+        {
+            self.uri = value
+                .try_into()
+                .map_err(|e| format!("error converting supplied value for uri: {}", e));
+            self
+        }
+    }
+    impl std::convert::TryFrom<Material> for super::Material {
+        type Error = String;
+        fn try_from(value: Material) -> Result<Self, String> {
+            Ok(Self {
+                digest: value.digest?,
+                uri: value.uri?,
+            })
+        }
+    }
+    impl From<super::Material> for Material {
+        fn from(value: super::Material) -> Self {
+            Self {
+                digest: Ok(value.digest),
+                uri: Ok(value.uri),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct Metadata {
+        finished_on: Result<Option<chrono::DateTime<chrono::offset::Utc>>, String>,
+        invocation_id: Result<String, String>,
+        started_on: Result<chrono::DateTime<chrono::offset::Utc>, String>,
+    }
+    impl Default for Metadata {
+        fn default() -> Self {
+            Self {
+                finished_on: Ok(Default::default()),
+                invocation_id: Err("no value supplied for invocation_id".to_string()),
+                started_on: Err("no value supplied for started_on".to_string()),
+            }
+        }
+    }
+    impl Metadata {
+        pub fn finished_on<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<chrono::DateTime<chrono::offset::Utc>>>,
+            T::Error: std::fmt::Display,
+        {
+            self.finished_on = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for finished_on: {}", e)
+                });
+            self
+        }
+        pub fn invocation_id<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<String>,
+            T::Error: std::fmt::Display,
+        {
+            self.invocation_id = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for invocation_id: {}", e)
+                });
+            self
+        }
+        pub fn started_on<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<chrono::DateTime<chrono::offset::Utc>>,
+            T::Error: std::fmt::Display,
+        {
+            self.started_on = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for started_on: {}", e)
+                });
+            self
+        }
+    }
+    impl std::convert::TryFrom<Metadata> for super::Metadata {
+        type Error = String;
+        fn try_from(value: Metadata) -> Result<Self, String> {
+            Ok(Self {
+                finished_on: value.finished_on?,
+                invocation_id: value.invocation_id?,
+                started_on: value.started_on?,
+            })
+        }
+    }
+    impl From<super::Metadata> for Metadata {
+        fn from(value: super::Metadata) -> Self {
+            Self {
+                finished_on: Ok(value.finished_on),
+                invocation_id: Ok(value.invocation_id),
+                started_on: Ok(value.started_on),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct Predicate {
+        subtype_0: Result<Option<super::SlsaProvenanceV1Predicate>, String>,
+        subtype_1: Result<Option<super::SlsaProvenanceV02Predicate>, String>,
+        subtype_2: Result<Option<super::SlsaVerificationSummaryV1Predicate>, String>,
+        subtype_3: Result<Option<serde_json::Value>, String>,
+    }
+    impl Default for Predicate {
+        fn default() -> Self {
+            Self {
+                subtype_0: Ok(Default::default()),
+                subtype_1: Ok(Default::default()),
+                subtype_2: Ok(Default::default()),
+                subtype_3: Ok(Default::default()),
             }
         }
     }
@@ -583,8 +1112,7 @@ pub mod builder {
             T: std::convert::TryInto<Option<super::SlsaProvenanceV1Predicate>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .subtype_0 = value
+            self.subtype_0 = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for subtype_0: {}", e)
@@ -593,17 +1121,40 @@ pub mod builder {
         }
         pub fn subtype_1<T>(mut self, value: T) -> Self
         where
-            T: std::convert::TryInto<Option<serde_json::Value>>,
+            T: std::convert::TryInto<Option<super::SlsaProvenanceV02Predicate>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .subtype_1 = value
+            self.subtype_1 = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for subtype_1: {}", e)
                 });
             self
         }
+        pub fn subtype_2<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<super::SlsaVerificationSummaryV1Predicate>>,
+            T::Error: std::fmt::Display,
+        {
+            self.subtype_2 = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for subtype_2: {}", e)
+                });
+            self
+        }
+        pub fn subtype_3<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<serde_json::Value>>,
+            T::Error: std::fmt::Display,
+        {
+            self.subtype_3 = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for subtype_3: {}", e)
+                });
+            self
+        }
     }
     impl std::convert::TryFrom<Predicate> for super::Predicate {
         type Error = String;
@@ -611,6 +1162,8 @@ pub mod builder {
             Ok(Self {
                 subtype_0: value.subtype_0?,
                 subtype_1: value.subtype_1?,
+                subtype_2: value.subtype_2?,
+                subtype_3: value.subtype_3?,
             })
         }
     }
@@ -619,6 +1172,8 @@ pub mod builder {
             Self {
                 subtype_0: Ok(value.subtype_0),
                 subtype_1: Ok(value.subtype_1),
+                subtype_2: Ok(value.subtype_2),
+                subtype_3: Ok(value.subtype_3),
             }
         }
     }
@@ -626,11 +1181,11 @@ pub mod builder {
     pub struct ResourceDescriptor {
         annotations: Result<Option<serde_json::Value>, String>,
         content: Result<Option<String>, String>,
-        digest: Result<Option<std::collections::HashMap<String, String>>, String>,
+        digest: Result<Option<super::DigestSet>, String>,
         download_location: Result<Option<String>, String>,
         media_type: Result<Option<String>, String>,
         name: Result<Option<String>, String>,
-        uri: Result<String, String>,
+        uri: Result<Option<String>, String>,
     }
     impl Default for ResourceDescriptor {
         fn default() -> Self {
@@ -641,7 +1196,7 @@ pub mod builder {
                 download_location: Ok(Default::default()),
                 media_type: Ok(Default::default()),
                 name: Ok(Default::default()),
-                uri: Err("no value supplied for uri".to_string()),
+                uri: Ok(Default::default()),
             }
         }
     }
@@ -651,8 +1206,7 @@ pub mod builder {
             T: std::convert::TryInto<Option<serde_json::Value>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .annotations = value
+            self.annotations = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for annotations: {}", e)
@@ -664,8 +1218,7 @@ pub mod builder {
             T: std::convert::TryInto<Option<String>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .content = value
+            self.content = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for content: {}", e)
@@ -674,11 +1227,10 @@ pub mod builder {
         }
         pub fn digest<T>(mut self, value: T) -> Self
         where
-            T: std::convert::TryInto<Option<std::collections::HashMap<String, String>>>,
+            T: std::convert::TryInto<Option<super::DigestSet>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .digest = value
+            self.digest = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for digest: {}", e)
@@ -690,8 +1242,7 @@ pub mod builder {
             T: std::convert::TryInto<Option<String>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .download_location = value
+            self.download_location = value
                 .try_into()
                 .map_err(|e| {
                     format!(
@@ -705,8 +1256,7 @@ pub mod builder {
             T: std::convert::TryInto<Option<String>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .media_type = value
+            self.media_type = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for media_type: {}", e)
@@ -718,19 +1268,17 @@ pub mod builder {
             T: std::convert::TryInto<Option<String>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .name = value
+            self.name = value
                 .try_into()
                 .map_err(|e| format!("error converting supplied value for name: {}", e));
             self
         }
         pub fn uri<T>(mut self, value: T) -> Self
         where
-            T: std::convert::TryInto<String>,
+            T: std::convert::TryInto<Option<String>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .uri = value
+            self.uri = value
                 .try_into()
                 .map_err(|e| format!("error converting supplied value for uri: {}", e));
             self
@@ -784,8 +1332,7 @@ pub mod builder {
             T: std::convert::TryInto<super::Builder>,
             T::Error: std::fmt::Display,
         {
-            self
-                .builder = value
+            self.builder = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for builder: {}", e)
@@ -797,8 +1344,7 @@ pub mod builder {
             T: std::convert::TryInto<Option<Vec<super::ResourceDescriptor>>>,
             T::Error: std::fmt::Display,
         {
-            self
-                .byproducts = value
+            self.byproducts = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for byproducts: {}", e)
@@ -810,8 +1356,7 @@ pub mod builder {
             T: std::convert::TryInto<super::Metadata>,
             T::Error: std::fmt::Display,
         {
-            self
-                .metadata = value
+            self.metadata = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for metadata: {}", e)
@@ -839,6 +1384,273 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    pub struct SlsaProvenanceV02Builder {
+        id: Result<String, String>,
+    }
+    impl Default for SlsaProvenanceV02Builder {
+        fn default() -> Self {
+            Self {
+                id: Err("no value supplied for id".to_string()),
+            }
+        }
+    }
+    impl SlsaProvenanceV02Builder {
+        pub fn id<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<String>,
+            T::Error: std::fmt::Display,
+        {
+            self.id = value
+                .try_into()
+                .map_err(|e| format!("error converting supplied value for id: {}", e));
+            self
+        }
+    }
+    impl std::convert::TryFrom<SlsaProvenanceV02Builder>
+    for super::SlsaProvenanceV02Builder {
+        type Error = String;
+        fn try_from(value: SlsaProvenanceV02Builder) -> Result<Self, String> {
+            Ok(Self { id: value.id? })
+        }
+    }
+    impl From<super::SlsaProvenanceV02Builder> for SlsaProvenanceV02Builder {
+        fn from(value: super::SlsaProvenanceV02Builder) -> Self {
+            Self { id: Ok(value.id) }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct SlsaProvenanceV02Metadata {
+        build_finished_on: Result<Option<chrono::DateTime<chrono::offset::Utc>>, String>,
+        build_invocation_id: Result<Option<String>, String>,
+        build_started_on: Result<Option<chrono::DateTime<chrono::offset::Utc>>, String>,
+        completeness: Result<Option<super::Completeness>, String>,
+        reproducible: Result<Option<bool>, String>,
+    }
+    impl Default for SlsaProvenanceV02Metadata {
+        fn default() -> Self {
+            Self {
+                build_finished_on: Ok(Default::default()),
+                build_invocation_id: Ok(Default::default()),
+                build_started_on: Ok(Default::default()),
+                completeness: Ok(Default::default()),
+                reproducible: Ok(Default::default()),
+            }
+        }
+    }
+    impl SlsaProvenanceV02Metadata {
+        pub fn build_finished_on<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<chrono::DateTime<chrono::offset::Utc>>>,
+            T::Error: std::fmt::Display,
+        {
+            self.build_finished_on = value
+                .try_into()
+                .map_err(|e| {
+                    format!(
+                        "error converting supplied value for build_finished_on: {}", e
+                    )
+                });
+            self
+        }
+        pub fn build_invocation_id<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<String>>,
+            T::Error: std::fmt::Display,
+        {
+            self.build_invocation_id = value
+                .try_into()
+                .map_err(|e| {
+                    format!(
+                        "error converting supplied value for build_invocation_id: {}", e
+                    )
+                });
+            self
+        }
+        pub fn build_started_on<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<chrono::DateTime<chrono::offset::Utc>>>,
+            T::Error: std::fmt::Display,
+        {
+            self.build_started_on = value
+                .try_into()
+                .map_err(|e| {
+                    format!(
+                        "error converting supplied value for build_started_on: {}", e
+                    )
+                });
+            self
+        }
+        pub fn completeness<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<super::Completeness>>,
+            T::Error: std::fmt::Display,
+        {
+            self.completeness = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for completeness: {}", e)
+                });
+            self
+        }
+        pub fn reproducible<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<bool>>,
+            T::Error: std::fmt::Display,
+        {
+            self.reproducible = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for reproducible: {}", e)
+                });
+            self
+        }
+    }
+    impl std::convert::TryFrom<SlsaProvenanceV02Metadata>
+    for super::SlsaProvenanceV02Metadata {
+        type Error = String;
+        fn try_from(value: SlsaProvenanceV02Metadata) -> Result<Self, String> {
+            Ok(Self {
+                build_finished_on: value.build_finished_on?,
+                build_invocation_id: value.build_invocation_id?,
+                build_started_on: value.build_started_on?,
+                completeness: value.completeness?,
+                reproducible: value.reproducible?,
+            })
+        }
+    }
+    impl From<super::SlsaProvenanceV02Metadata> for SlsaProvenanceV02Metadata {
+        fn from(value: super::SlsaProvenanceV02Metadata) -> Self {
+            Self {
+                build_finished_on: Ok(value.build_finished_on),
+                build_invocation_id: Ok(value.build_invocation_id),
+                build_started_on: Ok(value.build_started_on),
+                completeness: Ok(value.completeness),
+                reproducible: Ok(value.reproducible),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct SlsaProvenanceV02Predicate {
+        build_config: Result<Option<serde_json::Value>, String>,
+        build_type: Result<String, String>,
+        builder: Result<super::SlsaProvenanceV02Builder, String>,
+        invocation: Result<Option<super::Invocation>, String>,
+        materials: Result<Option<Vec<super::Material>>, String>,
+        metadata: Result<Option<super::SlsaProvenanceV02Metadata>, String>,
+    }
+    impl Default for SlsaProvenanceV02Predicate {
+        fn default() -> Self {
+            Self {
+                build_config: Ok(Default::default()),
+                build_type: Err("no value supplied for build_type".to_string()),
+                builder: Err("no value supplied for builder".to_string()),
+                invocation: Ok(Default::default()),
+                materials: Ok(Default::default()),
+                metadata: Ok(Default::default()),
+            }
+        }
+    }
+    impl SlsaProvenanceV02Predicate {
+        pub fn build_config<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<serde_json::Value>>,
+            T::Error: std::fmt::Display,
+        {
+            self.build_config = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for build_config: {}", e)
+                });
+            self
+        }
+        pub fn build_type<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<String>,
+            T::Error: std::fmt::Display,
+        {
+            self.build_type = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for build_type: {}", e)
+                });
+            self
+        }
+        pub fn builder<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<super::SlsaProvenanceV02Builder>,
+            T::Error: std::fmt::Display,
+        {
+            self.builder = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for builder: {}", e)
+                });
+            self
+        }
+        pub fn invocation<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<super::Invocation>>,
+            T::Error: std::fmt::Display,
+        {
+            self.invocation = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for invocation: {}", e)
+                });
+            self
+        }
+        pub fn materials<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<Vec<super::Material>>>,
+            T::Error: std::fmt::Display,
+        {
+            self.materials = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for materials: {}", e)
+                });
+            self
+        }
+        pub fn metadata<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<super::SlsaProvenanceV02Metadata>>,
+            T::Error: std::fmt::Display,
+        {
+            self.metadata = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for metadata: {}", e)
+                });
+            self
+        }
+    }
+    impl std::convert::TryFrom<SlsaProvenanceV02Predicate>
+    for super::SlsaProvenanceV02Predicate {
+        type Error = String;
+        fn try_from(value: SlsaProvenanceV02Predicate) -> Result<Self, String> {
+            Ok(Self {
+                build_config: value.build_config?,
+                build_type: value.build_type?,
+                builder: value.builder?,
+                invocation: value.invocation?,
+                materials: value.materials?,
+                metadata: value.metadata?,
+            })
+        }
+    }
+    impl From<super::SlsaProvenanceV02Predicate> for SlsaProvenanceV02Predicate {
+        fn from(value: super::SlsaProvenanceV02Predicate) -> Self {
+            Self {
+                build_config: Ok(value.build_config),
+                build_type: Ok(value.build_type),
+                builder: Ok(value.builder),
+                invocation: Ok(value.invocation),
+                materials: Ok(value.materials),
+                metadata: Ok(value.metadata),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct SlsaProvenanceV1Predicate {
         build_definition: Result<super::BuildDefinition, String>,
         run_details: Result<super::RunDetails, String>,
@@ -859,8 +1671,7 @@ pub mod builder {
             T: std::convert::TryInto<super::BuildDefinition>,
             T::Error: std::fmt::Display,
         {
-            self
-                .build_definition = value
+            self.build_definition = value
                 .try_into()
                 .map_err(|e| {
                     format!(
@@ -874,8 +1685,7 @@ pub mod builder {
             T: std::convert::TryInto<super::RunDetails>,
             T::Error: std::fmt::Display,
         {
-            self
-                .run_details = value
+            self.run_details = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for run_details: {}", e)
@@ -902,6 +1712,189 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    pub struct SlsaVerificationSummaryV1Predicate {
+        dependency_levels: Result<
+            Option<std::collections::HashMap<String, u64>>,
+            String,
+        >,
+        input_attestations: Result<Option<Vec<super::ResourceDescriptor>>, String>,
+        policy: Result<super::ResourceDescriptor, String>,
+        resource_uri: Result<String, String>,
+        slsa_version: Result<Option<String>, String>,
+        time_verified: Result<chrono::DateTime<chrono::offset::Utc>, String>,
+        verification_result: Result<super::VerificationResult, String>,
+        verified_levels: Result<Vec<String>, String>,
+        verifier: Result<super::Verifier, String>,
+    }
+    impl Default for SlsaVerificationSummaryV1Predicate {
+        fn default() -> Self {
+            Self {
+                dependency_levels: Ok(Default::default()),
+                input_attestations: Ok(Default::default()),
+                policy: Err("no value supplied for policy".to_string()),
+                resource_uri: Err("no value supplied for resource_uri".to_string()),
+                slsa_version: Ok(Default::default()),
+                time_verified: Err("no value supplied for time_verified".to_string()),
+                verification_result: Err(
+                    "no value supplied for verification_result".to_string(),
+                ),
+                verified_levels: Err(
+                    "no value supplied for verified_levels".to_string(),
+                ),
+                verifier: Err("no value supplied for verifier".to_string()),
+            }
+        }
+    }
+    impl SlsaVerificationSummaryV1Predicate {
+        pub fn dependency_levels<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<std::collections::HashMap<String, u64>>>,
+            T::Error: std::fmt::Display,
+        {
+            self.dependency_levels = value
+                .try_into()
+                .map_err(|e| {
+                    format!(
+                        "error converting supplied value for dependency_levels: {}", e
+                    )
+                });
+            self
+        }
+        pub fn input_attestations<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<Vec<super::ResourceDescriptor>>>,
+            T::Error: std::fmt::Display,
+        {
+            self.input_attestations = value
+                .try_into()
+                .map_err(|e| {
+                    format!(
+                        "error converting supplied value for input_attestations: {}", e
+                    )
+                });
+            self
+        }
+        pub fn policy<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<super::ResourceDescriptor>,
+            T::Error: std::fmt::Display,
+        {
+            self.policy = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for policy: {}", e)
+                });
+            self
+        }
+        pub fn resource_uri<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<String>,
+            T::Error: std::fmt::Display,
+        {
+            self.resource_uri = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for resource_uri: {}", e)
+                });
+            self
+        }
+        pub fn slsa_version<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<String>>,
+            T::Error: std::fmt::Display,
+        {
+            self.slsa_version = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for slsa_version: {}", e)
+                });
+            self
+        }
+        pub fn time_verified<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<chrono::DateTime<chrono::offset::Utc>>,
+            T::Error: std::fmt::Display,
+        {
+            self.time_verified = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for time_verified: {}", e)
+                });
+            self
+        }
+        pub fn verification_result<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<super::VerificationResult>,
+            T::Error: std::fmt::Display,
+        {
+            self.verification_result = value
+                .try_into()
+                .map_err(|e| {
+                    format!(
+                        "error converting supplied value for verification_result: {}", e
+                    )
+                });
+            self
+        }
+        pub fn verified_levels<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Vec<String>>,
+            T::Error: std::fmt::Display,
+        {
+            self.verified_levels = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for verified_levels: {}", e)
+                });
+            self
+        }
+        pub fn verifier<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<super::Verifier>,
+            T::Error: std::fmt::Display,
+        {
+            self.verifier = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for verifier: {}", e)
+                });
+            self
+        }
+    }
+    impl std::convert::TryFrom<SlsaVerificationSummaryV1Predicate>
+    for super::SlsaVerificationSummaryV1Predicate {
+        type Error = String;
+        fn try_from(value: SlsaVerificationSummaryV1Predicate) -> Result<Self, String> {
+            Ok(Self {
+                dependency_levels: value.dependency_levels?,
+                input_attestations: value.input_attestations?,
+                policy: value.policy?,
+                resource_uri: value.resource_uri?,
+                slsa_version: value.slsa_version?,
+                time_verified: value.time_verified?,
+                verification_result: value.verification_result?,
+                verified_levels: value.verified_levels?,
+                verifier: value.verifier?,
+            })
+        }
+    }
+    impl From<super::SlsaVerificationSummaryV1Predicate>
+    for SlsaVerificationSummaryV1Predicate {
+        fn from(value: super::SlsaVerificationSummaryV1Predicate) -> Self {
+            Self {
+                dependency_levels: Ok(value.dependency_levels),
+                input_attestations: Ok(value.input_attestations),
+                policy: Ok(value.policy),
+                resource_uri: Ok(value.resource_uri),
+                slsa_version: Ok(value.slsa_version),
+                time_verified: Ok(value.time_verified),
+                verification_result: Ok(value.verification_result),
+                verified_levels: Ok(value.verified_levels),
+                verifier: Ok(value.verifier),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct Subject {
         digest: Result<super::DigestSet, String>,
         name: Result<String, String>,
@@ -920,8 +1913,7 @@ pub mod builder {
             T: std::convert::TryInto<super::DigestSet>,
             T::Error: std::fmt::Display,
         {
-            self
-                .digest = value
+            self.digest = value
                 .try_into()
                 .map_err(|e| {
                     format!("error converting supplied value for digest: {}", e)
@@ -933,8 +1925,7 @@ pub mod builder {
             T: std::convert::TryInto<String>,
             T::Error: std::fmt::Display,
         {
-            self
-                .name = value
+            self.name = value
                 .try_into()
                 .map_err(|e| format!("error converting supplied value for name: {}", e));
             self
@@ -957,5 +1948,59 @@ pub mod builder {
             }
         }
     }
+    #[derive(Clone, Debug)]
+    pub struct Verifier {
+        id: Result<String, String>,
+        version: Result<Option<std::collections::HashMap<String, String>>, String>,
+    }
+    impl Default for Verifier {
+        fn default() -> Self {
+            Self {
+                id: Err("no value supplied for id".to_string()),
+                version: Ok(Default::default()),
+            }
+        }
+    }
+    impl Verifier {
+        pub fn id<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<String>,
+            T::Error: std::fmt::Display,
+        {
+            self.id = value
+                .try_into()
+                .map_err(|e| format!("error converting supplied value for id: {}", e));
+            self
+        }
+        pub fn version<T>(mut self, value: T) -> Self
+        where
+            T: std::convert::TryInto<Option<std::collections::HashMap<String, String>>>,
+            T::Error: std::fmt::Display,
+        {
+            self.version = value
+                .try_into()
+                .map_err(|e| {
+                    format!("error converting supplied value for version: {}", e)
+                });
+            self
+        }
+    }
+    impl std::convert::TryFrom<Verifier> for super::Verifier {
+        type Error = String;
+        fn try_from(value: Verifier) -> Result<Self, String> {
+            Ok(Self {
+                id: value.id?,
+                version: value.version?,
+            })
+        }
+    }
+    impl From<super::Verifier> for Verifier {
+        fn from(value: super::Verifier) -> Self {
+            Self {
+                id: Ok(value.id),
+                version: Ok(value.version),
+            }
+        }
+    }
 }
 